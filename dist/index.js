@@ -131,6 +131,19 @@ function buildArgs() {
     'patch-pre-release-tag',
     'commit-message-incrementing',
     'assembly-informational-format',
+    'commit-date-source',
+    'scheme',
+    'cal-ver-format',
+    'include-remote-branches',
+    'pre-release-format',
+    'tag-selection',
+    'build-metadata-format',
+    'rebuild-counter-env',
+    'feature-label-prefix',
+    'output-file',
+    'output-file-format',
+    'branch-precedence',
+    'build-metadata-mode',
     'config',
   ].forEach((name) => {
     const value = getInput(name);
@@ -140,6 +153,11 @@ function buildArgs() {
   [
     'continuous-delivery',
     'as-release',
+    'no-metadata',
+    'loose-tag-versions',
+    'require-signed-tags',
+    'increment-preview',
+    'case-insensitive-branches',
     'show-config',
     'verbose',
   ].forEach((name) => {
@@ -0,0 +1,40 @@
+#ifndef GIT_VERSIONER_H
+#define GIT_VERSIONER_H
+
+/* Generated with cbindgen, do not edit. Regenerate with: cbindgen --config cbindgen.toml --crate git-versioner --output include/git_versioner.h */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Calculates the version of the repository at `path` and returns it as JSON.
+ *
+ * `config_json` may be null or a JSON object with the fields of the configuration file
+ * (e.g. `{"PreReleaseTag": "alpha"}`). Returns null on failure, see `git_versioner_last_error`.
+ *
+ * # Safety
+ *
+ * `path` and `config_json` must be null or point to nul-terminated strings.
+ * The returned string must be released with `git_versioner_free`.
+ */
+char *git_versioner_calculate(const char *path, const char *config_json);
+
+/**
+ * Releases a string returned by `git_versioner_calculate`.
+ *
+ * # Safety
+ *
+ * `value` must be null or a pointer returned by `git_versioner_calculate` not freed before.
+ */
+void git_versioner_free(char *value);
+
+/**
+ * Returns the error of the last failed call on this thread, or null if it succeeded.
+ *
+ * The string is owned by the library and valid until the next call on this thread.
+ */
+const char *git_versioner_last_error(void);
+
+#endif  /* GIT_VERSIONER_H */
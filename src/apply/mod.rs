@@ -0,0 +1,409 @@
+use crate::GitVersion;
+use crate::exporter::docker_tag_of;
+use anyhow::{Result, anyhow};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+// Sets the project version of a pom and, on request, of its parent reference and module poms.
+// Only the version texts are replaced, so formatting and comments stay untouched.
+pub fn apply_maven(
+    version: &GitVersion,
+    pom: &Path,
+    include_parent: bool,
+    modules: bool,
+) -> Result<Vec<PathBuf>> {
+    let mut poms = vec![pom.to_path_buf()];
+    let mut updated = Vec::new();
+    let mut index = 0;
+    while let Some(pom) = poms.get(index).cloned() {
+        index += 1;
+        let xml = fs::read_to_string(&pom)
+            .map_err(|e| anyhow!("Could not read pom {}: {}", pom.display(), e))?;
+
+        let mut versions = Vec::new();
+        versions.extend(
+            element_texts(&xml, &["project", "version"])
+                .into_iter()
+                .next(),
+        );
+        if include_parent {
+            versions.extend(
+                element_texts(&xml, &["project", "parent", "version"])
+                    .into_iter()
+                    .next(),
+            );
+        }
+        if versions.is_empty() && index == 1 {
+            return Err(anyhow!("Pom {} has no <version> to set", pom.display()));
+        }
+
+        if modules {
+            let directory = pom.parent().unwrap_or(Path::new("."));
+            for module in element_texts(&xml, &["project", "modules", "module"]) {
+                let module = directory.join(xml[module].trim());
+                poms.push(match module.extension() {
+                    Some(_) => module, // Points to the pom file itself
+                    None => module.join("pom.xml"),
+                });
+            }
+        }
+
+        if versions.is_empty() {
+            continue; // Modules usually inherit the version of their parent
+        }
+        let mut xml = xml;
+        versions.sort_by_key(|range| std::cmp::Reverse(range.start));
+        for range in versions {
+            // SemVer renders pre-releases as 1.2.0-SNAPSHOT if Snapshot is enabled
+            xml.replace_range(range, &version.sem_ver);
+        }
+        fs::write(&pom, xml)?;
+        updated.push(pom);
+    }
+    Ok(updated)
+}
+
+// Sets the version property of a properties file, inserting it if missing. All other lines,
+// including comments and their line endings, stay untouched.
+pub fn apply_gradle(version: &GitVersion, file: &Path, key: &str) -> Result<Vec<PathBuf>> {
+    let content = fs::read_to_string(file)
+        .map_err(|e| anyhow!("Could not read properties file {}: {}", file.display(), e))?;
+    let line_ending = match content.contains("\r\n") {
+        true => "\r\n",
+        false => "\n",
+    };
+
+    let mut updated = String::with_capacity(content.len());
+    let mut found = false;
+    for line in content.split_inclusive('\n') {
+        match property_value_in(line, key) {
+            Some(value) if !found => {
+                found = true;
+                updated.push_str(&line[..value.start]);
+                updated.push_str(&version.sem_ver);
+                updated.push_str(&line[value.end..]);
+            }
+            _ => updated.push_str(line),
+        }
+    }
+    if !found {
+        if !updated.is_empty() && !updated.ends_with('\n') {
+            updated.push_str(line_ending);
+        }
+        updated.push_str(&format!("{key}={}{line_ending}", version.sem_ver));
+    }
+
+    fs::write(file, updated)?;
+    Ok(vec![file.to_path_buf()])
+}
+
+// Byte range of the value of a "key=value", "key: value" or "key value" line
+fn property_value_in(line: &str, key: &str) -> Option<Range<usize>> {
+    let content = line.trim_end_matches(['\r', '\n']);
+    let indentation = content.len() - content.trim_start().len();
+    let rest = content[indentation..].strip_prefix(key)?;
+    let separator = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+    let rest = &rest[separator..];
+    let start = match rest.strip_prefix(['=', ':']) {
+        Some(value) => content.len() - value.trim_start_matches([' ', '\t']).len(),
+        None if separator > 0 => content.len() - rest.len(),
+        None => return None, // Longer key with the same prefix, e.g. "versionCode"
+    };
+    Some(start..content.len())
+}
+
+// Sets the appVersion of a chart to the FullSemVer and its version to the SemVer, which Helm
+// requires to be a semantic version without build metadata. Only the values are replaced, so
+// comments, quoting and key order stay untouched.
+pub fn apply_helm(
+    version: &GitVersion,
+    chart: &Path,
+    app_version_only: bool,
+) -> Result<Vec<PathBuf>> {
+    let file = match chart.is_dir() {
+        true => chart.join("Chart.yaml"),
+        false => chart.to_path_buf(),
+    };
+    let mut content = fs::read_to_string(&file)
+        .map_err(|e| anyhow!("Could not read chart {}: {}", file.display(), e))?;
+
+    if !app_version_only {
+        content = with_yaml_value(&content, "version", &version.sem_ver)
+            .ok_or_else(|| anyhow!("Chart {} has no version to set", file.display()))?;
+    }
+    content = match with_yaml_value(&content, "appVersion", &version.full_sem_ver) {
+        Some(content) => content,
+        None => {
+            let line_ending = match content.contains("\r\n") {
+                true => "\r\n",
+                false => "\n",
+            };
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push_str(line_ending);
+            }
+            content + &format!(r#"appVersion: "{}"{line_ending}"#, version.full_sem_ver)
+        }
+    };
+
+    fs::write(&file, content)?;
+    Ok(vec![file])
+}
+
+// Sets the newTag of an image in the images list of a kustomization to the Docker tag of the
+// FullSemVer, adding the entry or the whole list if missing. All other lines stay untouched.
+pub fn apply_kustomize(version: &GitVersion, file: &Path, image: &str) -> Result<Vec<PathBuf>> {
+    let mut content = fs::read_to_string(file)
+        .map_err(|e| anyhow!("Could not read kustomization {}: {}", file.display(), e))?;
+    let tag = docker_tag_of(&version.full_sem_ver);
+    let line_ending = match content.contains("\r\n") {
+        true => "\r\n",
+        false => "\n",
+    };
+
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for line in content.split_inclusive('\n') {
+        lines.push((start, line));
+        start += line.len();
+    }
+
+    let Some(images) = lines.iter().position(|(_, line)| {
+        line.strip_prefix("images:")
+            .is_some_and(|rest| rest.trim().is_empty() || rest.trim().starts_with('#'))
+    }) else {
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push_str(line_ending);
+        }
+        content.push_str(&format!(
+            "images:{line_ending}  - name: {image}{line_ending}    newTag: {tag}{line_ending}"
+        ));
+        fs::write(file, content)?;
+        return Ok(vec![file.to_path_buf()]);
+    };
+
+    let mut entries: Vec<ImageEntry> = Vec::new();
+    let (images_start, images_line) = lines[images];
+    let mut section_end = images_start + images_line.len();
+    for &(start, line) in &lines[images + 1..] {
+        let text = line.trim_end_matches(['\r', '\n']);
+        let trimmed = text.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indentation = text.len() - trimmed.len();
+        if indentation == 0 && !trimmed.starts_with('-') {
+            break; // Next top level key
+        }
+        section_end = start + line.len();
+
+        let (field, field_indentation) = match trimmed.strip_prefix('-') {
+            Some(item) => {
+                let field = item.trim_start_matches(' ');
+                let field_indentation = match field.is_empty() {
+                    true => indentation + 2, // Fields start on the next line
+                    false => text.len() - field.len(),
+                };
+                entries.push(ImageEntry {
+                    indentation,
+                    field_indentation,
+                    name: None,
+                    new_tag: None,
+                    end: section_end,
+                });
+                (field, field_indentation)
+            }
+            None => (trimmed, indentation),
+        };
+        let Some(entry) = entries.last_mut() else {
+            continue;
+        };
+        entry.end = section_end;
+        if field_indentation != entry.field_indentation {
+            continue; // Nested value of a field
+        }
+        if let Some(value) = yaml_field_value(field, "name") {
+            entry.name = Some(field[value].trim_matches(['"', '\'']).to_string());
+        } else if let Some(value) = yaml_field_value(field, "newTag") {
+            let offset = start + field_indentation;
+            entry.new_tag = Some(offset + value.start..offset + value.end);
+        }
+    }
+
+    let matches = entries
+        .iter()
+        .filter(|entry| entry.name.as_deref() == Some(image))
+        .collect::<Vec<_>>();
+    match matches.as_slice() {
+        [] => {
+            let indentation = " ".repeat(entries.first().map_or(2, |entry| entry.indentation));
+            content.insert_str(
+                section_end,
+                &format!(
+                    "{}{indentation}- name: {image}{line_ending}{indentation}  newTag: {tag}{line_ending}",
+                    missing_line_ending(&content[..section_end], line_ending)
+                ),
+            );
+        }
+        [entry] => match &entry.new_tag {
+            Some(range) => {
+                let quote = match content[range.clone()].chars().next() {
+                    Some(quote @ ('"' | '\'')) => quote.to_string(),
+                    _ => String::new(),
+                };
+                content.replace_range(range.clone(), &format!("{quote}{tag}{quote}"));
+            }
+            None => {
+                let indentation = " ".repeat(entry.field_indentation);
+                content.insert_str(
+                    entry.end,
+                    &format!(
+                        "{}{indentation}newTag: {tag}{line_ending}",
+                        missing_line_ending(&content[..entry.end], line_ending)
+                    ),
+                );
+            }
+        },
+        _ => {
+            return Err(anyhow!(
+                r#"Image "{}" is listed {} times in kustomization {}"#,
+                image,
+                matches.len(),
+                file.display()
+            ));
+        }
+    }
+
+    fs::write(file, content)?;
+    Ok(vec![file.to_path_buf()])
+}
+
+struct ImageEntry {
+    indentation: usize,
+    field_indentation: usize,
+    name: Option<String>,
+    new_tag: Option<Range<usize>>,
+    end: usize,
+}
+
+// Byte range of the value of a "key: value" field, including quotes but no trailing comment
+fn yaml_field_value(field: &str, key: &str) -> Option<Range<usize>> {
+    let rest = field.strip_prefix(key)?.strip_prefix(':')?;
+    let start = field.len() - rest.trim_start_matches([' ', '\t']).len();
+    let value = &field[start..];
+    let value = value.find(" #").map_or(value, |comment| &value[..comment]);
+    Some(start..start + value.trim_end().len())
+}
+
+fn missing_line_ending<'a>(content: &str, line_ending: &'a str) -> &'a str {
+    match content.is_empty() || content.ends_with('\n') {
+        true => "",
+        false => line_ending,
+    }
+}
+
+// Writes an output variable followed by a newline. An up to date file is left untouched, so build
+// systems tracking its modification time do not rebuild.
+pub fn apply_version_file(
+    version: &GitVersion,
+    file: &Path,
+    variable: &str,
+    check: bool,
+) -> Result<Vec<PathBuf>> {
+    let content = match &serde_json::to_value(version)?[variable] {
+        serde_json::Value::Null => {
+            return Err(anyhow!(
+                r#"Invalid value "{}" for variable. Should be an output variable like "FullSemVer"."#,
+                variable
+            ));
+        }
+        serde_json::Value::String(value) => format!("{value}\n"),
+        value => format!("{value}\n"),
+    };
+
+    if fs::read_to_string(file).is_ok_and(|existing| existing == content) {
+        return Ok(Vec::new());
+    }
+    if check {
+        return Err(anyhow!(
+            "Version file {} is stale, expected {}",
+            file.display(),
+            content.trim_end()
+        ));
+    }
+    fs::write(file, content)?;
+    Ok(vec![file.to_path_buf()])
+}
+
+// Replaces the value of a top level "key: value" line, keeping its quotes and trailing comment
+fn with_yaml_value(content: &str, key: &str, value: &str) -> Option<String> {
+    let mut start = 0;
+    for line in content.split_inclusive('\n') {
+        if let Some(rest) = line.strip_prefix(key)
+            && let Some(rest) = rest.strip_prefix(':')
+        {
+            let value_start = start + line.len() - rest.trim_start_matches([' ', '\t']).len();
+            let rest = rest.trim_end_matches(['\r', '\n']);
+            let rest = rest
+                .find(" #")
+                .map_or(rest, |comment| &rest[..comment])
+                .trim();
+            let value_end = value_start + rest.len();
+            let quote = match rest.chars().next() {
+                Some(quote @ ('"' | '\'')) => quote.to_string(),
+                _ => String::new(),
+            };
+            let separator = match value_start == start + key.len() + 1 {
+                true => " ", // e.g. "version:" without value
+                false => "",
+            };
+            let mut content = content.to_string();
+            content.replace_range(
+                value_start..value_end,
+                &format!("{separator}{quote}{value}{quote}"),
+            );
+            return Some(content);
+        }
+        start += line.len();
+    }
+    None
+}
+
+// Byte ranges of the texts of all elements at the given path from the root element. Comments,
+// processing instructions and CDATA sections are skipped, so commented out elements never match.
+fn element_texts(xml: &str, path: &[&str]) -> Vec<Range<usize>> {
+    let mut texts = Vec::new();
+    let mut elements: Vec<&str> = Vec::new();
+    let mut position = 0;
+    while let Some(offset) = xml[position..].find('<') {
+        let start = position + offset;
+        let rest = &xml[start..];
+        let terminator = match rest {
+            _ if rest.starts_with("<!--") => "-->",
+            _ if rest.starts_with("<![CDATA[") => "]]>",
+            _ => ">",
+        };
+        let Some(length) = rest.find(terminator) else {
+            break; // Truncated document
+        };
+        let end = start + length + terminator.len();
+        let tag = &xml[start + 1..end - 1];
+        position = end;
+
+        if tag.starts_with(['!', '?']) || tag.ends_with('/') {
+            continue; // Comments, declarations and empty elements have no text
+        }
+        if tag.starts_with('/') {
+            elements.pop();
+            continue;
+        }
+        elements.push(tag.split(char::is_whitespace).next().unwrap_or_default());
+        if elements == path {
+            let text_end = xml[end..]
+                .find('<')
+                .map_or(xml.len(), |offset| end + offset);
+            texts.push(end..text_end);
+        }
+    }
+    texts
+}
@@ -1,11 +1,25 @@
 use anyhow::anyhow;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
+use std::io::{self, Read};
+use std::ops::Deref;
 use std::path::{Path, PathBuf};
 
 const DEFAULT_CONFIG_FILE_NAME: &str = ".git-versioner";
+const TOOL_TABLE: &str = "git-versioner";
 const CONFIG_FILE_EXTENSIONS: [&str; 3] = ["toml", "yaml", "yml"];
+const LEGACY_CONFIG_FILE_NAMES: [&str; 6] = [
+    "GitVersion.yml",
+    "GitVersion.yaml",
+    ".gitversion.yml",
+    ".gitversion.yaml",
+    "gitversion.yml",
+    "gitversion.yaml",
+];
+const LEGACY_BRANCH_NAME_GROUP: &str = "BranchName";
+const CONFIG_FROM_STDIN: &str = "-";
 
 pub const MAIN_BRANCH: &str = r"^(trunk|main|master)$";
 pub const RELEASE_BRANCH: &str = r"^releases?[/-](?<BranchName>.+)$";
@@ -15,6 +29,37 @@ pub const PRE_RELEASE_TAG: &str = "pre";
 pub const PATCH_PRE_RELEASE_TAG: &str = "";
 pub const COMMIT_MESSAGE_INCREMENTING: &str = "Disabled";
 pub const ASSEMBLY_INFORMATIONAL_FORMAT: &str = "{InformationalVersion}";
+pub const COMMIT_DATE_SOURCE: &str = "CommitterUtc";
+pub const SCHEME: &str = "SemVer";
+pub const CAL_VER_FORMAT: &str = "YYYY.MM.PATCH";
+pub const INCLUDE_REMOTE_BRANCHES: bool = true;
+pub const PRE_RELEASE_FORMAT: &str = "Counted";
+pub const TAG_SELECTION: &str = "highest-version";
+pub const BUILD_METADATA_FORMAT: &str = "";
+pub const REBUILD_COUNTER_ENV: &str = "";
+pub const FEATURE_LABEL_PREFIX: &str = "";
+pub const OUTPUT_FILE_FORMAT: &str = "json";
+pub const BRANCH_PRECEDENCE: &str = "main,release,feature";
+pub const BUILD_METADATA_MODE: &str = "none";
+pub const PLAN_OUTPUT: &str = "human";
+pub const OUTPUT: &str = "json";
+pub const SEM_VER_FORMAT: &str = "";
+pub const OUTPUT_ENV_FILE_VAR: &str = "";
+pub const ANNOTATIONS: bool = true;
+pub const TEAMCITY_SYSTEM_PARAMETERS: bool = true;
+pub const TEAMCITY_PARAMETER_PREFIX: &str = "GitVersion";
+pub const SNAPSHOT: bool = false;
+pub const INCREMENT_ON_FIRST_COMMIT: bool = true;
+pub const TAG_MESSAGE_CHANNEL: bool = false;
+pub const PRE_RELEASE_NUMBER_PADDING: u64 = 0;
+pub const PREFERRED_TAG_PREFIX: &str = "v";
+pub const FEATURE_BRANCH_MODE: &str = "PreRelease";
+pub const TAG_MESSAGE_TEMPLATE: &str = "Release {FullSemVer}";
+pub const BUMP_SCAN: &str = "All";
+pub const IGNORE_PRERELEASE_TAGS: bool = false;
+pub const CASE_INSENSITIVE_BRANCHES: bool = false;
+pub const APPLY_REPLACE_REFS: bool = false;
+pub const TRUNK_PRE_RELEASE_TAG: &str = "";
 
 pub const NO_BRANCH_NAME: &str = "(no branch)";
 pub const PRERELEASE_WEIGHT_MAIN: u64 = 55000;
@@ -25,8 +70,8 @@ pub const PRERELEASE_WEIGHT_FEATURE: u64 = 30000;
 pub trait Configuration {
     fn path(&self) -> &PathBuf;
     fn main_branch(&self) -> &str;
-    fn release_branch(&self) -> &str;
-    fn feature_branch(&self) -> &str;
+    fn release_branch(&self) -> &[String];
+    fn feature_branch(&self) -> &[String];
     fn tag_prefix(&self) -> &str;
     fn pre_release_tag(&self) -> &str;
     fn patch_pre_release_tag(&self) -> &str;
@@ -34,47 +79,333 @@ pub trait Configuration {
     fn assembly_informational_format(&self) -> &str {
         ASSEMBLY_INFORMATIONAL_FORMAT
     }
+    fn commit_date_source(&self) -> &str {
+        COMMIT_DATE_SOURCE
+    }
+    fn scheme(&self) -> &str {
+        SCHEME
+    }
+    fn cal_ver_format(&self) -> &str {
+        CAL_VER_FORMAT
+    }
+    fn include_remote_branches(&self) -> &bool {
+        &INCLUDE_REMOTE_BRANCHES
+    }
+    fn pre_release_format(&self) -> &str {
+        PRE_RELEASE_FORMAT
+    }
+    fn tag_selection(&self) -> &str {
+        TAG_SELECTION
+    }
+    fn build_metadata_format(&self) -> &str {
+        BUILD_METADATA_FORMAT
+    }
+    fn rebuild_counter_env(&self) -> &str {
+        REBUILD_COUNTER_ENV
+    }
+    fn feature_label_prefix(&self) -> &str {
+        FEATURE_LABEL_PREFIX
+    }
+    fn branch_precedence(&self) -> &str {
+        BRANCH_PRECEDENCE
+    }
+    fn build_metadata_mode(&self) -> &str {
+        BUILD_METADATA_MODE
+    }
+    fn sem_ver_format(&self) -> &str {
+        SEM_VER_FORMAT
+    }
+    fn output_env_file_var(&self) -> &str {
+        OUTPUT_ENV_FILE_VAR
+    }
+    fn annotations(&self) -> &bool {
+        &ANNOTATIONS
+    }
+    fn teamcity_system_parameters(&self) -> &bool {
+        &TEAMCITY_SYSTEM_PARAMETERS
+    }
+    fn teamcity_parameter_prefix(&self) -> &str {
+        TEAMCITY_PARAMETER_PREFIX
+    }
+    fn export_variables(&self) -> &[String] {
+        &[]
+    }
+    fn omit_fields(&self) -> &[String] {
+        &[]
+    }
+    fn pr_label_bumps(&self) -> &[String] {
+        &[]
+    }
+    fn snapshot(&self) -> &bool {
+        &SNAPSHOT
+    }
+    fn increment_on_first_commit(&self) -> &bool {
+        &INCREMENT_ON_FIRST_COMMIT
+    }
+    fn tag_message_channel(&self) -> &bool {
+        &TAG_MESSAGE_CHANNEL
+    }
+    fn pre_release_number_padding(&self) -> &u64 {
+        &PRE_RELEASE_NUMBER_PADDING
+    }
+    fn preferred_tag_prefix(&self) -> &str {
+        PREFERRED_TAG_PREFIX
+    }
+    fn feature_branch_mode(&self) -> &str {
+        FEATURE_BRANCH_MODE
+    }
+    fn tag_message_template(&self) -> &str {
+        TAG_MESSAGE_TEMPLATE
+    }
+    fn bump_scan(&self) -> &str {
+        BUMP_SCAN
+    }
+    fn ignore_prerelease_tags(&self) -> &bool {
+        &IGNORE_PRERELEASE_TAGS
+    }
+    fn apply_replace_refs(&self) -> &bool {
+        &APPLY_REPLACE_REFS
+    }
+    fn trunk_pre_release_tag(&self) -> &str {
+        TRUNK_PRE_RELEASE_TAG
+    }
     fn continuous_delivery(&self) -> &bool {
         &false
     }
     fn verbose(&self) -> &bool {
         &false
     }
+    fn loose_tag_versions(&self) -> &bool {
+        &false
+    }
+    fn require_signed_tags(&self) -> &bool {
+        &false
+    }
+    fn case_insensitive_branches(&self) -> &bool {
+        &CASE_INSENSITIVE_BRANCHES
+    }
     fn as_release(&self) -> &bool {
         &false
     }
+    fn no_metadata(&self) -> &bool {
+        &false
+    }
+    fn fail_on_dirty(&self) -> &bool {
+        &false
+    }
+    fn strict_semver(&self) -> &bool {
+        &false
+    }
+    fn strict_sources(&self) -> &bool {
+        &false
+    }
+    fn weight_base(&self) -> &Option<u64> {
+        &None
+    }
+    fn branch_config(&self) -> &[String] {
+        &[]
+    }
+    fn increment_preview(&self) -> &bool {
+        &false
+    }
+    fn compare(&self) -> &[String] {
+        &[]
+    }
+    fn create_tag(&self) -> &bool {
+        &false
+    }
+    fn list_version_sources(&self) -> &bool {
+        &false
+    }
+    fn all_branches(&self) -> &bool {
+        &false
+    }
+    fn output(&self) -> &str {
+        OUTPUT
+    }
+    fn output_file(&self) -> &Option<PathBuf> {
+        &None
+    }
+    fn output_file_format(&self) -> &str {
+        OUTPUT_FILE_FORMAT
+    }
+    fn gitlab_release_file(&self) -> &Option<PathBuf> {
+        &None
+    }
+    fn docker_args_file(&self) -> &Option<PathBuf> {
+        &None
+    }
+    fn docker_labels_file(&self) -> &Option<PathBuf> {
+        &None
+    }
     fn show_config(&self) -> &bool {
         &false
     }
+    fn print_graph(&self) -> &bool {
+        &false
+    }
+    fn command(&self) -> &Option<Command> {
+        &None
+    }
 
     fn print(&self) -> DefaultConfig {
         DefaultConfig {
-            path: fs::canonicalize(self.path()).unwrap(),
+            path: canonicalize(self.path()).unwrap(),
             main_branch: self.main_branch().to_string(),
-            release_branch: self.release_branch().to_string(),
-            feature_branch: self.feature_branch().to_string(),
+            release_branch: self.release_branch().into(),
+            feature_branch: self.feature_branch().into(),
             tag_prefix: self.tag_prefix().to_string(),
             pre_release_tag: self.pre_release_tag().to_string(),
             patch_pre_release_tag: self.patch_pre_release_tag().to_string(),
             commit_message_incrementing: self.commit_message_incrementing().to_string(),
             assembly_informational_format: self.assembly_informational_format().to_string(),
+            commit_date_source: self.commit_date_source().to_string(),
+            scheme: self.scheme().to_string(),
+            cal_ver_format: self.cal_ver_format().to_string(),
+            include_remote_branches: *self.include_remote_branches(),
+            pre_release_format: self.pre_release_format().to_string(),
+            tag_selection: self.tag_selection().to_string(),
+            build_metadata_format: self.build_metadata_format().to_string(),
+            rebuild_counter_env: self.rebuild_counter_env().to_string(),
+            feature_label_prefix: self.feature_label_prefix().to_string(),
+            branch_precedence: self.branch_precedence().to_string(),
+            build_metadata_mode: self.build_metadata_mode().to_string(),
+            sem_ver_format: self.sem_ver_format().to_string(),
+            output_env_file_var: self.output_env_file_var().to_string(),
+            annotations: *self.annotations(),
+            teamcity_system_parameters: *self.teamcity_system_parameters(),
+            teamcity_parameter_prefix: self.teamcity_parameter_prefix().to_string(),
+            export_variables: self.export_variables().to_vec(),
+            omit_fields: self.omit_fields().to_vec(),
+            pr_label_bumps: self.pr_label_bumps().to_vec(),
+            snapshot: *self.snapshot(),
+            increment_on_first_commit: *self.increment_on_first_commit(),
+            tag_message_channel: *self.tag_message_channel(),
+            pre_release_number_padding: *self.pre_release_number_padding(),
+            preferred_tag_prefix: self.preferred_tag_prefix().to_string(),
+            feature_branch_mode: self.feature_branch_mode().to_string(),
+            tag_message_template: self.tag_message_template().to_string(),
+            bump_scan: self.bump_scan().to_string(),
+            ignore_prerelease_tags: *self.ignore_prerelease_tags(),
+            case_insensitive_branches: *self.case_insensitive_branches(),
+            apply_replace_refs: *self.apply_replace_refs(),
+            trunk_pre_release_tag: self.trunk_pre_release_tag().to_string(),
             continuous_delivery: *self.continuous_delivery(),
         }
     }
 }
 
+pub fn canonicalize<P: AsRef<Path>>(path: P) -> std::io::Result<PathBuf> {
+    fs::canonicalize(path).map(|path| without_verbatim_prefix(&path))
+}
+
+pub fn without_verbatim_prefix(path: &Path) -> PathBuf {
+    const VERBATIM_PREFIX: &str = r"\\?\";
+    const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+    let Some(raw) = path.to_str() else {
+        return path.to_path_buf();
+    };
+
+    if let Some(share) = raw.strip_prefix(VERBATIM_UNC_PREFIX) {
+        return PathBuf::from(format!(r"\\{share}"));
+    }
+
+    match raw.strip_prefix(VERBATIM_PREFIX) {
+        Some(rest) if rest.chars().nth(1) == Some(':') => PathBuf::from(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+// A single pattern or a list of patterns, evaluated in order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BranchPatterns {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl Deref for BranchPatterns {
+    type Target = [String];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            BranchPatterns::Single(pattern) => std::slice::from_ref(pattern),
+            BranchPatterns::Multiple(patterns) => patterns,
+        }
+    }
+}
+
+impl From<&str> for BranchPatterns {
+    fn from(pattern: &str) -> Self {
+        BranchPatterns::Single(pattern.to_string())
+    }
+}
+
+impl From<String> for BranchPatterns {
+    fn from(pattern: String) -> Self {
+        BranchPatterns::Single(pattern)
+    }
+}
+
+impl From<&[String]> for BranchPatterns {
+    fn from(patterns: &[String]) -> Self {
+        match patterns {
+            [pattern] => BranchPatterns::Single(pattern.clone()),
+            _ => BranchPatterns::Multiple(patterns.to_vec()),
+        }
+    }
+}
+
+impl<const N: usize> From<[&str; N]> for BranchPatterns {
+    fn from(patterns: [&str; N]) -> Self {
+        BranchPatterns::from(patterns.map(String::from).as_slice())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct DefaultConfig {
     pub path: PathBuf,
     pub main_branch: String,
-    pub release_branch: String,
-    pub feature_branch: String,
+    pub release_branch: BranchPatterns,
+    pub feature_branch: BranchPatterns,
     pub tag_prefix: String,
     pub pre_release_tag: String,
     pub patch_pre_release_tag: String,
     pub commit_message_incrementing: String,
     pub assembly_informational_format: String,
+    pub commit_date_source: String,
+    pub scheme: String,
+    pub cal_ver_format: String,
+    pub include_remote_branches: bool,
+    pub pre_release_format: String,
+    pub tag_selection: String,
+    pub build_metadata_format: String,
+    pub rebuild_counter_env: String,
+    pub feature_label_prefix: String,
+    pub branch_precedence: String,
+    pub build_metadata_mode: String,
+    pub sem_ver_format: String,
+    pub output_env_file_var: String,
+    pub annotations: bool,
+    pub teamcity_system_parameters: bool,
+    pub teamcity_parameter_prefix: String,
+    pub export_variables: Vec<String>,
+    pub omit_fields: Vec<String>,
+    pub pr_label_bumps: Vec<String>,
+    pub snapshot: bool,
+    pub increment_on_first_commit: bool,
+    pub tag_message_channel: bool,
+    pub pre_release_number_padding: u64,
+    pub preferred_tag_prefix: String,
+    pub feature_branch_mode: String,
+    pub tag_message_template: String,
+    pub bump_scan: String,
+    pub ignore_prerelease_tags: bool,
+    pub case_insensitive_branches: bool,
+    pub apply_replace_refs: bool,
+    pub trunk_pre_release_tag: String,
     pub continuous_delivery: bool,
 }
 
@@ -82,14 +413,45 @@ pub struct DefaultConfig {
 #[serde(rename_all = "PascalCase")]
 pub struct ConfigurationFile {
     pub main_branch: Option<String>,
-    pub release_branch: Option<String>,
-    pub feature_branch: Option<String>,
+    pub release_branch: Option<BranchPatterns>,
+    pub feature_branch: Option<BranchPatterns>,
     pub tag_prefix: Option<String>,
     pub pre_release_tag: Option<String>,
     pub patch_pre_release_tag: Option<String>,
     pub commit_message_incrementing: Option<String>,
     #[serde(alias = "assembly-informational-format")]
     pub assembly_informational_format: Option<String>,
+    pub commit_date_source: Option<String>,
+    pub scheme: Option<String>,
+    pub cal_ver_format: Option<String>,
+    pub include_remote_branches: Option<bool>,
+    pub pre_release_format: Option<String>,
+    pub tag_selection: Option<String>,
+    pub build_metadata_format: Option<String>,
+    pub rebuild_counter_env: Option<String>,
+    pub feature_label_prefix: Option<String>,
+    pub branch_precedence: Option<String>,
+    pub build_metadata_mode: Option<String>,
+    pub sem_ver_format: Option<String>,
+    pub output_env_file_var: Option<String>,
+    pub annotations: Option<bool>,
+    pub teamcity_system_parameters: Option<bool>,
+    pub teamcity_parameter_prefix: Option<String>,
+    pub export_variables: Option<Vec<String>>,
+    pub omit_fields: Option<Vec<String>>,
+    pub pr_label_bumps: Option<Vec<String>>,
+    pub snapshot: Option<bool>,
+    pub increment_on_first_commit: Option<bool>,
+    pub tag_message_channel: Option<bool>,
+    pub pre_release_number_padding: Option<u64>,
+    pub preferred_tag_prefix: Option<String>,
+    pub feature_branch_mode: Option<String>,
+    pub tag_message_template: Option<String>,
+    pub bump_scan: Option<String>,
+    pub ignore_prerelease_tags: Option<bool>,
+    pub case_insensitive_branches: Option<bool>,
+    pub apply_replace_refs: Option<bool>,
+    pub trunk_pre_release_tag: Option<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -106,11 +468,19 @@ pub struct Args {
     #[arg(long, value_parser, help = "Regex to detect the main branch")]
     main_branch: Option<String>,
 
-    #[arg(long, value_parser, help = "Regex to detect the release branch(es)")]
-    release_branch: Option<String>,
+    #[arg(
+        long,
+        value_parser,
+        help = "Regex to detect the release branch(es), repeatable to match several patterns in order"
+    )]
+    release_branch: Option<Vec<String>>,
 
-    #[arg(long, value_parser, help = "Regex to detect the feature branch(es)")]
-    feature_branch: Option<String>,
+    #[arg(
+        long,
+        value_parser,
+        help = "Regex to detect the feature branch(es), repeatable to match several patterns in order"
+    )]
+    feature_branch: Option<Vec<String>>,
 
     #[arg(long, value_parser, help = "Regex to detect version tag(s)")]
     tag_prefix: Option<String>,
@@ -128,6 +498,13 @@ pub struct Args {
     )]
     patch_pre_release_tag: Option<String>,
 
+    #[arg(
+        long,
+        value_parser,
+        help = "Label to be used to mark pre-release versions on the trunk branch (e.g., ci, dev, etc.), default: <PRE_RELEASE_TAG>"
+    )]
+    trunk_pre_release_tag: Option<String>,
+
     #[arg(
         long,
         value_parser,
@@ -138,15 +515,40 @@ pub struct Args {
     #[arg(
         long,
         value_parser,
-        help = "Increment based on conventional commits ('Disabled' (default) or 'Enabled')",
-        long_help = r#"Increment considering conventional commits (values: 'Disabled' (default) or 'Enabled'):
+        help = "Accept tag versions with 1, 2 or 4 components (e.g., v2, v2.1, v2.1.0.3)"
+    )]
+    loose_tag_versions: bool,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Fail unless release tags used as version source are signed annotated tags"
+    )]
+    require_signed_tags: bool,
+
+    #[arg(
+        long,
+        value_parser,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        help = "Match trunk, release and feature branch patterns case-insensitively (default: false)"
+    )]
+    case_insensitive_branches: Option<bool>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Increment based on conventional commits ('Disabled' (default), 'Enabled' or 'MergeMessageOnly')",
+        long_help = r#"Increment considering conventional commits (values: 'Disabled' (default), 'Enabled' or 'MergeMessageOnly'):
 - Disabled: Incrementation will be based on tags and release branches only.
             After a release tag is created on the main branch (e.g. v1.2.0), the main branch will
             automatically be bumped to the next minor version (e.g. v1.3.0).
 - Enabled:  Incrementation will be based on tags, release branches and commits.
             Instead of bumping the minor version on the main branch after a feature release tag
             (e.g. v1.2.0), only the patch version will be incremented (e.g. v1.2.1) until a `feat:`
-            commit is encountered or a release branch is created."#
+            commit is encountered or a release branch is created.
+- MergeMessageOnly: Like Enabled, but only merge commit messages on the first-parent history
+            (e.g. pull request titles or squash commit subjects) are considered."#
     )]
     commit_message_incrementing: Option<String>,
 
@@ -157,21 +559,453 @@ pub struct Args {
     )]
     assembly_informational_format: Option<String>,
 
+    #[arg(
+        long,
+        value_parser,
+        help = "Date used for CommitDate output ('CommitterUtc' (default), 'CommitterLocal' or 'Author')",
+        long_help = r#"Date used for CommitDate output (values: 'CommitterUtc' (default), 'CommitterLocal' or 'Author'):
+- CommitterUtc:   Committer date converted to UTC.
+- CommitterLocal: Committer date in the timezone it was committed in.
+- Author:         Author date in the timezone it was authored in."#
+    )]
+    commit_date_source: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Versioning scheme ('SemVer' (default) or 'CalVer')",
+        long_help = r#"Versioning scheme (values: 'SemVer' (default) or 'CalVer'):
+- SemVer: Versions are incremented based on tags, release branches and, optionally, commits.
+- CalVer: Versions on the main branch are derived from the HEAD commit date using the CalVerFormat
+          (e.g. 2024.3.0), where the patch counts release tags already made in that month."#
+    )]
+    scheme: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Calendar version format for the CalVer scheme (e.g. 'YYYY.MM.PATCH' (default) or 'YY.0M.MICRO')"
+    )]
+    cal_ver_format: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Consider remote release branches as version sources (default: true)"
+    )]
+    include_remote_branches: Option<bool>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Pre-release numbering ('Counted' (default) or 'Dated')",
+        long_help = r#"Pre-release numbering (values: 'Counted' (default) or 'Dated'):
+- Counted: Pre-releases are numbered by commits or pre-release tags (e.g. 1.3.0-pre.4).
+- Dated:   Pre-releases contain the HEAD commit date (UTC) and the number of commits made on
+           that day since the version source (e.g. 1.3.0-pre.20240517.2)."#
+    )]
+    pre_release_format: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Source selection among tags of equal version ('highest-version' (default) or 'most-recent-commit')"
+    )]
+    tag_selection: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Format string for build metadata (e.g. 'build.{env:GITHUB_RUN_NUMBER ?? 0}')"
+    )]
+    build_metadata_format: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Environment variable with the build attempt, appended as 'rebuild.<N>' to the build metadata if greater than 1 (e.g. 'GITHUB_RUN_ATTEMPT')"
+    )]
+    rebuild_counter_env: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Prefix for pre-release labels of feature branches (e.g. 'f-' yields 'f-my-feature.1')"
+    )]
+    feature_label_prefix: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Order in which branch patterns classify a branch matching several of them (default: 'main,release,feature')"
+    )]
+    branch_precedence: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Build metadata added to versions ('none' (default), 'commits', 'sha' or 'commits.sha')",
+        long_help = r#"Build metadata added to versions (values: 'none' (default), 'commits', 'sha' or 'commits.sha'):
+- none:        Keeps the build metadata of the version source.
+- commits:     Commits since the version source (e.g. 1.2.0-pre.4+5).
+- sha:         Short SHA of the HEAD commit (e.g. 1.2.0-pre.4+Sha.abc1234).
+- commits.sha: Both of the above (e.g. 1.2.0-pre.4+5.Sha.abc1234)."#
+    )]
+    build_metadata_mode: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Format string rebuilding SemVer and FullSemVer from output variables (e.g. '{Major}.{Minor}.{Patch}')"
+    )]
+    sem_ver_format: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Environment variable holding a file to which output variables are appended as 'GitVersion_<Key>=<value>' lines"
+    )]
+    output_env_file_var: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Print a notice annotation with the version when running in GitHub Actions (default: true)"
+    )]
+    annotations: Option<bool>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Also set every TeamCity parameter as 'system.' parameter (default: true)"
+    )]
+    teamcity_system_parameters: Option<bool>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Root name of the TeamCity parameters (default: GitVersion)"
+    )]
+    teamcity_parameter_prefix: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Output variable to export to build agents, repeatable to export several (default: all)"
+    )]
+    export_variables: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Output variable to omit from the printed JSON and the output file, repeatable to omit several"
+    )]
+    omit_fields: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Pull request label to bump mapping as <label>=<major|minor|patch>, repeatable (default: disabled)"
+    )]
+    pr_label_bumps: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        value_parser,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        help = "Replace the pre-release of versions with SNAPSHOT as used by Maven and Gradle (default: false)"
+    )]
+    snapshot: Option<bool>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Count the first commit of a repository without version source as pre-release 1 instead of 0 (default: true)"
+    )]
+    increment_on_first_commit: Option<bool>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Read the Channel from a 'channel:' line of annotated tag messages, used as pre-release label at such tags (default: false)"
+    )]
+    tag_message_channel: Option<bool>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Zero-pad the pre-release number to this many digits, appended to the label like pre0009 (default: 0, off)"
+    )]
+    pre_release_number_padding: Option<u64>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Literal prefix of the TagName and NextVersionTag outputs, e.g. for creating tags if TagPrefix is a pattern (default: v)"
+    )]
+    preferred_tag_prefix: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Version of feature branches: PreRelease replaces the pre-release label by the branch name, Metadata keeps the base branch version and adds the branch name as build metadata (default: PreRelease)"
+    )]
+    feature_branch_mode: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Message of the annotated tag created by --create-tag, supporting output variables like {FullSemVer} (default: Release {FullSemVer})"
+    )]
+    tag_message_template: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Commits scanned for version bumps: All includes commits of merged branches, FirstParent only the mainline and merge messages (default: All)"
+    )]
+    bump_scan: Option<String>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Exclude pre-release tags from all version calculations, e.g. if another tool creates them (default: false)"
+    )]
+    ignore_prerelease_tags: Option<bool>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Honor git replace refs (refs/replace/*), e.g. from git replace --graft, when walking the commit history (default: false)"
+    )]
+    apply_replace_refs: Option<bool>,
+
     #[arg(short, long, help = "Forces release generation instead of pre-release")]
     as_release: bool,
 
+    #[arg(long, help = "Strips build metadata from all version outputs")]
+    no_metadata: bool,
+
+    #[arg(
+        long,
+        help = "Fail if the working tree has uncommitted changes, e.g. for release builds"
+    )]
+    fail_on_dirty: bool,
+
+    #[arg(
+        long,
+        help = "Fail instead of emitting assembly versions with components beyond 65535"
+    )]
+    strict_semver: bool,
+
+    #[arg(
+        long,
+        help = "Fail instead of warning (--verbose) if tags of the same version are on different commits"
+    )]
+    strict_sources: bool,
+
+    #[arg(
+        long,
+        help = "Base of WeightedPreReleaseNumber, replacing the default of the branch type"
+    )]
+    weight_base: Option<u64>,
+
+    #[arg(
+        long,
+        help = "Override tag and increment of matching branches, e.g. 'feature/.*:tag=alpha,increment=minor'"
+    )]
+    branch_config: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Print the current version and the version a release would yield, then exit"
+    )]
+    increment_preview: bool,
+
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["REF_A", "REF_B"],
+        help = "Print the versions of two references and whether the second is higher, then exit"
+    )]
+    compare: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Create an annotated tag named TagName on HEAD with the TagMessageTemplate as message, then exit"
+    )]
+    create_tag: bool,
+
+    #[arg(
+        long,
+        help = "Print every tag and release branch considered as version source, then exit"
+    )]
+    list_version_sources: bool,
+
+    #[arg(
+        long,
+        help = "Print the version of every local branch instead of the checked out one, then exit"
+    )]
+    all_branches: bool,
+
+    #[arg(
+        long,
+        value_parser,
+        default_value = OUTPUT,
+        help = "Output format, 'json', or the variables as written by 'env-github' or 'env-gitlab' (--all-branches: 'json' or 'jsonl')"
+    )]
+    output: String,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Path of a file to write the version to, in addition to stdout"
+    )]
+    output_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_parser,
+        default_value = OUTPUT_FILE_FORMAT,
+        help = "Format of the output file ('json', 'dotenv' or 'yaml')"
+    )]
+    output_file_format: String,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Path of a GitLab release file for the next release, as JSON ('.json') or YAML"
+    )]
+    gitlab_release_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Path of a file with Docker build arguments, one KEY=VALUE line each"
+    )]
+    docker_args_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Path of a file with OCI image labels, one KEY=VALUE line each"
+    )]
+    docker_labels_file: Option<PathBuf>,
+
     #[arg(long, help = "Print effective configuration and exit")]
     show_config: bool,
 
+    #[arg(
+        long,
+        help = "Print the decorated commit graph to stderr, e.g. for bug reports"
+    )]
+    print_graph: bool,
+
     #[arg(short, long)]
     verbose: bool,
 
     #[arg(
         short = 'c',
         long = "config",
-        help = "Path to a configuration file (TOML or YAML)"
+        help = "Path to a configuration file (TOML or YAML), or '-' to read it from stdin"
     )]
     config_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_parser,
+        help = "Format of the configuration read from stdin (toml, yaml or json)"
+    )]
+    config_format: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum Command {
+    #[command(
+        about = "Print what a release from the current HEAD would entail (versions, tag, commits, breaking changes)"
+    )]
+    Plan {
+        #[arg(
+            long,
+            value_parser = ["human", "json"],
+            default_value = PLAN_OUTPUT,
+            help = "Format of the release plan"
+        )]
+        output: String,
+    },
+    #[command(
+        about = "Verify that a tag states the version its commit yields as release, e.g. before publishing a pushed tag"
+    )]
+    VerifyTag {
+        #[arg(help = "Name of the tag, e.g. v1.2.0")]
+        tag: String,
+    },
+    #[command(about = "Write the version into the build files of an ecosystem")]
+    Apply {
+        #[command(subcommand)]
+        target: ApplyTarget,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum ApplyTarget {
+    #[command(about = "Set the <version> of a Maven project, keeping formatting and comments")]
+    Maven {
+        #[arg(long, default_value = "pom.xml", help = "Path of the project's pom")]
+        pom: PathBuf,
+        #[arg(long, help = "Also set the <version> of the <parent>")]
+        include_parent: bool,
+        #[arg(long, help = "Also set the versions of the <modules>, recursively")]
+        modules: bool,
+    },
+    #[command(about = "Set the version property of a Gradle project, keeping all other lines")]
+    Gradle {
+        #[arg(
+            long,
+            default_value = "gradle.properties",
+            help = "Path of the properties file"
+        )]
+        file: PathBuf,
+        #[arg(long, default_value = "version", help = "Name of the version property")]
+        key: String,
+    },
+    #[command(about = "Set version and appVersion of a Helm chart, keeping comments and key order")]
+    Helm {
+        #[arg(
+            long,
+            default_value = "Chart.yaml",
+            help = "Path of the chart directory or its Chart.yaml"
+        )]
+        chart: PathBuf,
+        #[arg(long, help = "Only set the appVersion, keeping the chart version")]
+        app_version_only: bool,
+    },
+    #[command(about = "Set the newTag of an image of a kustomization, keeping all other lines")]
+    Kustomize {
+        #[arg(
+            long,
+            default_value = "kustomization.yaml",
+            help = "Path of the kustomization file"
+        )]
+        file: PathBuf,
+        #[arg(long, help = "Name of the image in the images list")]
+        image: String,
+    },
+    #[command(
+        about = "Write an output variable to a file, only touching it if the content differs"
+    )]
+    VersionFile {
+        #[arg(long, default_value = "VERSION", help = "Path of the version file")]
+        path: PathBuf,
+        #[arg(long, default_value = "FullSemVer", help = "Output variable to write")]
+        variable: String,
+        #[arg(long, help = "Fail if the file is stale instead of writing it")]
+        check: bool,
+    },
 }
 
 #[derive(Debug)]
@@ -186,18 +1020,179 @@ impl Default for DefaultConfig {
         Self {
             path: ".".into(),
             main_branch: MAIN_BRANCH.to_string(),
-            release_branch: RELEASE_BRANCH.to_string(),
-            feature_branch: FEATURE_BRANCH.to_string(),
+            release_branch: RELEASE_BRANCH.into(),
+            feature_branch: FEATURE_BRANCH.into(),
             tag_prefix: TAG_PREFIX.to_string(),
             pre_release_tag: PRE_RELEASE_TAG.to_string(),
             patch_pre_release_tag: PATCH_PRE_RELEASE_TAG.to_string(),
             commit_message_incrementing: COMMIT_MESSAGE_INCREMENTING.to_string(),
             assembly_informational_format: ASSEMBLY_INFORMATIONAL_FORMAT.to_string(),
+            commit_date_source: COMMIT_DATE_SOURCE.to_string(),
+            scheme: SCHEME.to_string(),
+            cal_ver_format: CAL_VER_FORMAT.to_string(),
+            include_remote_branches: INCLUDE_REMOTE_BRANCHES,
+            pre_release_format: PRE_RELEASE_FORMAT.to_string(),
+            tag_selection: TAG_SELECTION.to_string(),
+            build_metadata_format: BUILD_METADATA_FORMAT.to_string(),
+            rebuild_counter_env: REBUILD_COUNTER_ENV.to_string(),
+            feature_label_prefix: FEATURE_LABEL_PREFIX.to_string(),
+            branch_precedence: BRANCH_PRECEDENCE.to_string(),
+            build_metadata_mode: BUILD_METADATA_MODE.to_string(),
+            sem_ver_format: SEM_VER_FORMAT.to_string(),
+            output_env_file_var: OUTPUT_ENV_FILE_VAR.to_string(),
+            annotations: ANNOTATIONS,
+            teamcity_system_parameters: TEAMCITY_SYSTEM_PARAMETERS,
+            teamcity_parameter_prefix: TEAMCITY_PARAMETER_PREFIX.to_string(),
+            export_variables: Vec::new(),
+            omit_fields: Vec::new(),
+            pr_label_bumps: Vec::new(),
+            snapshot: SNAPSHOT,
+            increment_on_first_commit: INCREMENT_ON_FIRST_COMMIT,
+            tag_message_channel: TAG_MESSAGE_CHANNEL,
+            pre_release_number_padding: PRE_RELEASE_NUMBER_PADDING,
+            preferred_tag_prefix: PREFERRED_TAG_PREFIX.to_string(),
+            feature_branch_mode: FEATURE_BRANCH_MODE.to_string(),
+            tag_message_template: TAG_MESSAGE_TEMPLATE.to_string(),
+            bump_scan: BUMP_SCAN.to_string(),
+            ignore_prerelease_tags: IGNORE_PRERELEASE_TAGS,
+            case_insensitive_branches: CASE_INSENSITIVE_BRANCHES,
+            apply_replace_refs: APPLY_REPLACE_REFS,
+            trunk_pre_release_tag: TRUNK_PRE_RELEASE_TAG.to_string(),
             continuous_delivery: false,
         }
     }
 }
 
+macro_rules! override_with {
+    ($config:ident, $overrides:ident, $($name:ident),+) => {
+        $(
+            if let Some(value) = $overrides.$name {
+                $config.$name = value;
+            }
+        )+
+    };
+}
+
+macro_rules! builder_setter {
+    ($name:ident, $type:ty) => {
+        pub fn $name(mut self, value: impl Into<$type>) -> Self {
+            self.config.$name = value.into();
+            self
+        }
+    };
+}
+
+impl DefaultConfig {
+    pub fn builder() -> DefaultConfigBuilder {
+        DefaultConfigBuilder::default()
+    }
+
+    pub fn with_overrides(mut self, overrides: ConfigurationFile) -> Self {
+        override_with!(
+            self,
+            overrides,
+            main_branch,
+            release_branch,
+            feature_branch,
+            tag_prefix,
+            pre_release_tag,
+            patch_pre_release_tag,
+            commit_message_incrementing,
+            assembly_informational_format,
+            commit_date_source,
+            scheme,
+            cal_ver_format,
+            include_remote_branches,
+            pre_release_format,
+            tag_selection,
+            build_metadata_format,
+            rebuild_counter_env,
+            feature_label_prefix,
+            branch_precedence,
+            build_metadata_mode,
+            sem_ver_format,
+            output_env_file_var,
+            annotations,
+            teamcity_system_parameters,
+            teamcity_parameter_prefix,
+            export_variables,
+            omit_fields,
+            pr_label_bumps,
+            snapshot,
+            increment_on_first_commit,
+            tag_message_channel,
+            pre_release_number_padding,
+            preferred_tag_prefix,
+            feature_branch_mode,
+            tag_message_template,
+            bump_scan,
+            ignore_prerelease_tags,
+            case_insensitive_branches,
+            apply_replace_refs,
+            trunk_pre_release_tag
+        );
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DefaultConfigBuilder {
+    config: DefaultConfig,
+}
+
+impl DefaultConfigBuilder {
+    builder_setter!(path, PathBuf);
+    builder_setter!(main_branch, String);
+    builder_setter!(release_branch, BranchPatterns);
+    builder_setter!(feature_branch, BranchPatterns);
+    builder_setter!(tag_prefix, String);
+    builder_setter!(pre_release_tag, String);
+    builder_setter!(patch_pre_release_tag, String);
+    builder_setter!(commit_message_incrementing, String);
+    builder_setter!(assembly_informational_format, String);
+    builder_setter!(commit_date_source, String);
+    builder_setter!(scheme, String);
+    builder_setter!(cal_ver_format, String);
+    builder_setter!(include_remote_branches, bool);
+    builder_setter!(pre_release_format, String);
+    builder_setter!(tag_selection, String);
+    builder_setter!(build_metadata_format, String);
+    builder_setter!(rebuild_counter_env, String);
+    builder_setter!(feature_label_prefix, String);
+    builder_setter!(branch_precedence, String);
+    builder_setter!(build_metadata_mode, String);
+    builder_setter!(sem_ver_format, String);
+    builder_setter!(output_env_file_var, String);
+    builder_setter!(annotations, bool);
+    builder_setter!(teamcity_system_parameters, bool);
+    builder_setter!(teamcity_parameter_prefix, String);
+    builder_setter!(export_variables, Vec<String>);
+    builder_setter!(omit_fields, Vec<String>);
+    builder_setter!(pr_label_bumps, Vec<String>);
+    builder_setter!(snapshot, bool);
+    builder_setter!(increment_on_first_commit, bool);
+    builder_setter!(tag_message_channel, bool);
+    builder_setter!(pre_release_number_padding, u64);
+    builder_setter!(preferred_tag_prefix, String);
+    builder_setter!(feature_branch_mode, String);
+    builder_setter!(tag_message_template, String);
+    builder_setter!(bump_scan, String);
+    builder_setter!(ignore_prerelease_tags, bool);
+    builder_setter!(case_insensitive_branches, bool);
+    builder_setter!(apply_replace_refs, bool);
+    builder_setter!(trunk_pre_release_tag, String);
+    builder_setter!(continuous_delivery, bool);
+
+    pub fn overrides(mut self, overrides: ConfigurationFile) -> Self {
+        self.config = self.config.with_overrides(overrides);
+        self
+    }
+
+    pub fn build(self) -> DefaultConfig {
+        self.config
+    }
+}
+
 impl Configuration for DefaultConfig {
     fn path(&self) -> &PathBuf {
         &self.path
@@ -205,10 +1200,10 @@ impl Configuration for DefaultConfig {
     fn main_branch(&self) -> &str {
         &self.main_branch
     }
-    fn release_branch(&self) -> &str {
+    fn release_branch(&self) -> &[String] {
         &self.release_branch
     }
-    fn feature_branch(&self) -> &str {
+    fn feature_branch(&self) -> &[String] {
         &self.feature_branch
     }
     fn tag_prefix(&self) -> &str {
@@ -226,10 +1221,106 @@ impl Configuration for DefaultConfig {
     fn assembly_informational_format(&self) -> &str {
         &self.assembly_informational_format
     }
+    fn commit_date_source(&self) -> &str {
+        &self.commit_date_source
+    }
+    fn scheme(&self) -> &str {
+        &self.scheme
+    }
+    fn cal_ver_format(&self) -> &str {
+        &self.cal_ver_format
+    }
+    fn include_remote_branches(&self) -> &bool {
+        &self.include_remote_branches
+    }
+    fn pre_release_format(&self) -> &str {
+        &self.pre_release_format
+    }
+    fn tag_selection(&self) -> &str {
+        &self.tag_selection
+    }
+    fn build_metadata_format(&self) -> &str {
+        &self.build_metadata_format
+    }
+    fn rebuild_counter_env(&self) -> &str {
+        &self.rebuild_counter_env
+    }
+    fn feature_label_prefix(&self) -> &str {
+        &self.feature_label_prefix
+    }
+    fn branch_precedence(&self) -> &str {
+        &self.branch_precedence
+    }
+    fn build_metadata_mode(&self) -> &str {
+        &self.build_metadata_mode
+    }
+    fn sem_ver_format(&self) -> &str {
+        &self.sem_ver_format
+    }
+    fn output_env_file_var(&self) -> &str {
+        &self.output_env_file_var
+    }
+    fn annotations(&self) -> &bool {
+        &self.annotations
+    }
+    fn teamcity_system_parameters(&self) -> &bool {
+        &self.teamcity_system_parameters
+    }
+    fn teamcity_parameter_prefix(&self) -> &str {
+        &self.teamcity_parameter_prefix
+    }
+    fn export_variables(&self) -> &[String] {
+        &self.export_variables
+    }
+    fn omit_fields(&self) -> &[String] {
+        &self.omit_fields
+    }
+    fn pr_label_bumps(&self) -> &[String] {
+        &self.pr_label_bumps
+    }
+    fn snapshot(&self) -> &bool {
+        &self.snapshot
+    }
+    fn increment_on_first_commit(&self) -> &bool {
+        &self.increment_on_first_commit
+    }
+    fn tag_message_channel(&self) -> &bool {
+        &self.tag_message_channel
+    }
+    fn pre_release_number_padding(&self) -> &u64 {
+        &self.pre_release_number_padding
+    }
+    fn preferred_tag_prefix(&self) -> &str {
+        &self.preferred_tag_prefix
+    }
+    fn feature_branch_mode(&self) -> &str {
+        &self.feature_branch_mode
+    }
+    fn tag_message_template(&self) -> &str {
+        &self.tag_message_template
+    }
+    fn bump_scan(&self) -> &str {
+        &self.bump_scan
+    }
+    fn ignore_prerelease_tags(&self) -> &bool {
+        &self.ignore_prerelease_tags
+    }
+    fn case_insensitive_branches(&self) -> &bool {
+        &self.case_insensitive_branches
+    }
+    fn apply_replace_refs(&self) -> &bool {
+        &self.apply_replace_refs
+    }
+    fn trunk_pre_release_tag(&self) -> &str {
+        &self.trunk_pre_release_tag
+    }
+    fn continuous_delivery(&self) -> &bool {
+        &self.continuous_delivery
+    }
 }
 
 impl ConfigurationFile {
-    pub fn from_default_file() -> anyhow::Result<Self> {
+    pub fn from_default_file(verbose: bool) -> anyhow::Result<Self> {
         let mut last_error: Option<anyhow::Error> = None;
 
         for &ext in CONFIG_FILE_EXTENSIONS.iter() {
@@ -239,6 +1330,17 @@ impl ConfigurationFile {
             }
         }
 
+        // Eases migrating from GitVersion, whose files only count if no native one exists
+        for name in LEGACY_CONFIG_FILE_NAMES {
+            match fs::read_to_string(name) {
+                Ok(content) => {
+                    let legacy: LegacyConfigurationFile = serde_saphyr::from_str(&content)?;
+                    return Ok(legacy.into_configuration(verbose));
+                }
+                Err(err) => last_error = Some(err.into()),
+            }
+        }
+
         Err(last_error.unwrap())
     }
 
@@ -250,33 +1352,109 @@ impl ConfigurationFile {
             .ok_or_else(|| anyhow!("File has no extension"))?;
 
         match extension.to_lowercase().as_str() {
-            "toml" => Self::from_toml_file(path),
-            "yaml" | "yml" => Self::from_yaml_file(path),
+            "toml" | "yaml" | "yml" => Self::from_content(&fs::read_to_string(path)?, extension),
             _ => Err(anyhow!("Unsupported file format: {}", extension)),
         }
     }
 
-    fn from_toml_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
-        let content = fs::read_to_string(path)?;
-        let config: Self = toml::from_str(&content)?;
-        Ok(config)
+    pub fn from_stdin(format: Option<&str>) -> anyhow::Result<Self> {
+        let format = format.ok_or_else(|| {
+            anyhow!("Reading the configuration from stdin requires --config-format")
+        })?;
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content)?;
+        Self::from_content(&content, format)
     }
 
-    fn from_yaml_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
-        let content = fs::read_to_string(path)?;
-        let config: Self = serde_saphyr::from_str(&content)?;
+    fn from_content(content: &str, format: &str) -> anyhow::Result<Self> {
+        let config: Self = match format.to_lowercase().as_str() {
+            "toml" => {
+                // Files shared with other tools, like pyproject.toml, nest it as [tool.git-versioner]
+                let mut table: toml::Table = toml::from_str(content)?;
+                match table
+                    .get_mut("tool")
+                    .and_then(|tool| tool.as_table_mut())
+                    .and_then(|tool| tool.remove(TOOL_TABLE))
+                {
+                    Some(section) => section.try_into()?,
+                    None => table.try_into()?,
+                }
+            }
+            "yaml" | "yml" => serde_saphyr::from_str(content)?,
+            "json" => serde_json::from_str(content)?,
+            _ => return Err(anyhow!("Unsupported configuration format: {}", format)),
+        };
         Ok(config)
     }
 }
 
+// The subset of GitVersion's configuration with an equivalent in Git Versioner
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct LegacyConfigurationFile {
+    tag_prefix: Option<String>,
+    assembly_informational_format: Option<String>,
+    #[serde(default)]
+    branches: HashMap<String, LegacyBranchConfiguration>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct LegacyBranchConfiguration {
+    regex: Option<String>,
+}
+
+impl LegacyConfigurationFile {
+    fn into_configuration(mut self, verbose: bool) -> ConfigurationFile {
+        let main_branch = ["main", "master"]
+            .iter()
+            .find_map(|name| self.branch_regex(name));
+        ConfigurationFile {
+            main_branch,
+            release_branch: self.branch_patterns("release", verbose),
+            feature_branch: self.branch_patterns("feature", verbose),
+            tag_prefix: self.tag_prefix,
+            assembly_informational_format: self.assembly_informational_format,
+            ..Default::default()
+        }
+    }
+
+    fn branch_regex(&mut self, name: &str) -> Option<String> {
+        self.branches.remove(name).and_then(|branch| branch.regex)
+    }
+
+    // GitVersion 5 patterns like "^releases?[/-]" lack the BranchName group required here, so
+    // they are skipped in favor of the defaults
+    fn branch_patterns(&mut self, name: &str, verbose: bool) -> Option<BranchPatterns> {
+        let regex = self.branch_regex(name)?;
+        let has_branch_name = regex::Regex::new(&regex).is_ok_and(|regex| {
+            regex
+                .capture_names()
+                .flatten()
+                .any(|group| group == LEGACY_BRANCH_NAME_GROUP)
+        });
+        if !has_branch_name {
+            if verbose {
+                eprintln!(
+                    r#"GitVersion setting branches.{name}.regex "{regex}" is skipped, as it has no named group "{LEGACY_BRANCH_NAME_GROUP}""#
+                );
+            }
+            return None;
+        }
+        Some(BranchPatterns::from(regex))
+    }
+}
+
 pub fn load_configuration() -> anyhow::Result<ConfigurationLayers> {
     let args = Args::parse();
     let config = DefaultConfig::default();
     let file = match &args.config_file {
-        None => ConfigurationFile::from_default_file(),
-        Some(path) => ConfigurationFile::from_file(path),
-    }
-    .unwrap_or_default();
+        // Piped configuration has no fallback, so its errors are reported instead of ignored
+        Some(path) if path.as_os_str() == CONFIG_FROM_STDIN => {
+            ConfigurationFile::from_stdin(args.config_format.as_deref())?
+        }
+        None => ConfigurationFile::from_default_file(args.verbose).unwrap_or_default(),
+        Some(path) => ConfigurationFile::from_file(path).unwrap_or_default(),
+    };
     Ok(ConfigurationLayers { args, file, config })
 }
 
@@ -310,16 +1488,68 @@ macro_rules! config_getter {
 
 impl Configuration for ConfigurationLayers {
     config_getter!(main_branch, str, arg > file > default);
-    config_getter!(release_branch, str, arg > file > default);
-    config_getter!(feature_branch, str, arg > file > default);
+    config_getter!(release_branch, [String], arg > file > default);
+    config_getter!(feature_branch, [String], arg > file > default);
     config_getter!(tag_prefix, str, arg > file > default);
     config_getter!(pre_release_tag, str, arg > file > default);
     config_getter!(patch_pre_release_tag, str, arg > file > default);
     config_getter!(commit_message_incrementing, str, arg > file > default);
     config_getter!(assembly_informational_format, str, arg > file > default);
+    config_getter!(commit_date_source, str, arg > file > default);
+    config_getter!(scheme, str, arg > file > default);
+    config_getter!(cal_ver_format, str, arg > file > default);
+    config_getter!(include_remote_branches, bool, arg > file > default);
+    config_getter!(pre_release_format, str, arg > file > default);
+    config_getter!(tag_selection, str, arg > file > default);
+    config_getter!(build_metadata_format, str, arg > file > default);
+    config_getter!(rebuild_counter_env, str, arg > file > default);
+    config_getter!(feature_label_prefix, str, arg > file > default);
+    config_getter!(branch_precedence, str, arg > file > default);
+    config_getter!(build_metadata_mode, str, arg > file > default);
+    config_getter!(sem_ver_format, str, arg > file > default);
+    config_getter!(output_env_file_var, str, arg > file > default);
+    config_getter!(annotations, bool, arg > file > default);
+    config_getter!(teamcity_system_parameters, bool, arg > file > default);
+    config_getter!(teamcity_parameter_prefix, str, arg > file > default);
+    config_getter!(export_variables, [String], arg > file > default);
+    config_getter!(omit_fields, [String], arg > file > default);
+    config_getter!(pr_label_bumps, [String], arg > file > default);
+    config_getter!(snapshot, bool, arg > file > default);
+    config_getter!(increment_on_first_commit, bool, arg > file > default);
+    config_getter!(tag_message_channel, bool, arg > file > default);
+    config_getter!(pre_release_number_padding, u64, arg > file > default);
+    config_getter!(preferred_tag_prefix, str, arg > file > default);
+    config_getter!(feature_branch_mode, str, arg > file > default);
+    config_getter!(tag_message_template, str, arg > file > default);
+    config_getter!(bump_scan, str, arg > file > default);
+    config_getter!(ignore_prerelease_tags, bool, arg > file > default);
+    config_getter!(apply_replace_refs, bool, arg > file > default);
+    config_getter!(trunk_pre_release_tag, str, arg > file > default);
     config_getter!(continuous_delivery, bool, arg);
+    config_getter!(loose_tag_versions, bool, arg);
+    config_getter!(require_signed_tags, bool, arg);
+    config_getter!(case_insensitive_branches, bool, arg > file > default);
     config_getter!(path, PathBuf, arg > default);
     config_getter!(as_release, bool, arg);
+    config_getter!(no_metadata, bool, arg);
+    config_getter!(fail_on_dirty, bool, arg);
+    config_getter!(strict_semver, bool, arg);
+    config_getter!(strict_sources, bool, arg);
+    config_getter!(weight_base, Option<u64>, arg);
+    config_getter!(branch_config, [String], arg);
     config_getter!(verbose, bool, arg);
+    config_getter!(increment_preview, bool, arg);
+    config_getter!(compare, [String], arg);
+    config_getter!(create_tag, bool, arg);
+    config_getter!(list_version_sources, bool, arg);
+    config_getter!(all_branches, bool, arg);
+    config_getter!(output, str, arg);
+    config_getter!(output_file, Option<PathBuf>, arg);
+    config_getter!(output_file_format, str, arg);
+    config_getter!(gitlab_release_file, Option<PathBuf>, arg);
+    config_getter!(docker_args_file, Option<PathBuf>, arg);
+    config_getter!(docker_labels_file, Option<PathBuf>, arg);
     config_getter!(show_config, bool, arg);
+    config_getter!(print_graph, bool, arg);
+    config_getter!(command, Option<Command>, arg);
 }
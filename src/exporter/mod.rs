@@ -1,102 +1,457 @@
 use crate::GitVersion;
-use anyhow::Result;
+use crate::config::Configuration;
+use anyhow::{Result, anyhow};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::{SecondsFormat, Utc};
 use inflection_rs::inflection;
+use serde::Serialize;
 use std::env;
-use std::fs::OpenOptions;
+use std::ffi::OsStr;
+use std::fs::{self, OpenOptions};
 use std::io::Write;
+use std::path::Path;
 
 pub trait Exporter {
-    fn export(&self, version: &GitVersion) -> Result<()>;
+    // Formats the variables as written by export, free of IO
+    fn render(&self, version: &GitVersion, variables: &[(String, String)]) -> String;
+    fn export(&self, version: &GitVersion, variables: &[(String, String)]) -> Result<()>;
 }
 
-pub struct GitHubExporter;
+fn append_to(path: &OsStr, content: &str) -> Result<()> {
+    let mut file = OpenOptions::new().append(true).create(true).open(path)?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+// Local paths are too noisy for build agents, so they are only exported if configured explicitly
+const NOT_EXPORTED_BY_DEFAULT: [&str; 2] = ["RepositoryPath", "GitDir"];
+
+// Flattens the version into the key/value pairs all exporters emit, restricted to the configured
+// export variables unless none are configured
+pub fn variables_of(
+    version: &GitVersion,
+    export_variables: &[String],
+) -> Result<Vec<(String, String)>> {
+    let map = serde_json::to_value(version)?;
+    let map = map.as_object().unwrap();
+
+    check_names_in(map, export_variables, "ExportVariables")?;
+
+    Ok(flattened(map)
+        .into_iter()
+        .filter(|(key, _)| match export_variables.is_empty() {
+            true => !NOT_EXPORTED_BY_DEFAULT.contains(&key.as_str()),
+            false => export_variables.contains(key),
+        })
+        .collect())
+}
+
+// Fails for names that are no output variable of the version, suggesting similar ones
+pub fn check_variable_names(version: &GitVersion, names: &[String], option: &str) -> Result<()> {
+    let map = serde_json::to_value(version)?;
+    check_names_in(map.as_object().unwrap(), names, option)
+}
+
+fn check_names_in(
+    map: &serde_json::Map<String, serde_json::Value>,
+    names: &[String],
+    option: &str,
+) -> Result<()> {
+    let Some(unknown) = names.iter().find(|name| !map.contains_key(name.as_str())) else {
+        return Ok(());
+    };
+    let suggestions = map
+        .keys()
+        .filter(|key| edit_distance(&key.to_lowercase(), &unknown.to_lowercase()) <= 2)
+        .map(|key| format!(r#""{key}""#))
+        .collect::<Vec<_>>();
+    Err(match suggestions.is_empty() {
+        true => anyhow!(
+            r#"Invalid value "{}" for {}. Should only contain output variables like "FullSemVer"."#,
+            unknown,
+            option
+        ),
+        false => anyhow!(
+            r#"Invalid value "{}" for {}. Did you mean {}?"#,
+            unknown,
+            option,
+            suggestions.join(" or ")
+        ),
+    })
+}
+
+fn flattened(map: &serde_json::Map<String, serde_json::Value>) -> Vec<(String, String)> {
+    map.iter()
+        .map(|(key, raw_value)| {
+            let value = match raw_value {
+                serde_json::Value::String(s) => s.clone(),
+                _ => raw_value.to_string(),
+            };
+            (key.clone(), value)
+        })
+        .collect()
+}
+
+// Levenshtein distance, to suggest output variables for misspelled names
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right = right.chars().collect::<Vec<_>>();
+    let mut previous = (0..=right.len()).collect::<Vec<_>>();
+    for (i, left_char) in left.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, right_char) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left_char != *right_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
+
+pub struct GitHubExporter {
+    pub annotations: bool,
+}
 
 impl Exporter for GitHubExporter {
-    fn export(&self, version: &GitVersion) -> Result<()> {
-        if let Some(github_output_file) = env::var_os("GITHUB_OUTPUT") {
-            let map = serde_json::to_value(version)?;
-            let map = map.as_object().unwrap();
+    // Content appended to $GITHUB_OUTPUT
+    fn render(&self, _version: &GitVersion, variables: &[(String, String)]) -> String {
+        let mut content = String::new();
+        for (key, value) in variables {
+            content.push_str(&format!("GitVersion_{key}={value}\n"));
+            content.push_str(&format!(
+                "{}={value}\n",
+                inflection::camelize_upper(key, false)
+            ));
+        }
+        content
+    }
 
-            let mut file = OpenOptions::new()
-                .append(true)
-                .create(true)
-                .open(github_output_file)?;
-
-            for (key, raw_value) in map {
-                let value = match raw_value {
-                    serde_json::Value::String(s) => s.clone(),
-                    _ => raw_value.to_string(),
-                };
-                writeln!(file, "GitVersion_{key}={value}")?;
-                writeln!(file, "{}={value}", inflection::camelize_upper(key, false))?;
-            }
+    fn export(&self, version: &GitVersion, variables: &[(String, String)]) -> Result<()> {
+        if self.annotations {
+            println!(
+                "::notice title=git-versioner::{}",
+                escaped_workflow_command_data(&format!(
+                    "{} ({})",
+                    version.full_sem_ver, version.branch_name
+                ))
+            );
+        }
+
+        if let Some(github_output_file) = env::var_os("GITHUB_OUTPUT") {
+            append_to(&github_output_file, &self.render(version, variables))?;
         }
         Ok(())
     }
 }
 
+// Escapes the message of a workflow command, which GitHub would otherwise cut at line breaks
+fn escaped_workflow_command_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
 pub struct GitLabExporter;
 
 impl Exporter for GitLabExporter {
-    fn export(&self, version: &GitVersion) -> Result<()> {
+    // Content appended to $GITLAB_ENV
+    fn render(&self, _version: &GitVersion, variables: &[(String, String)]) -> String {
+        let mut content = String::new();
+        for (key, value) in variables {
+            content.push_str(&format!("GitVersion_{key}={value}\n"));
+        }
+        content
+    }
+
+    fn export(&self, version: &GitVersion, variables: &[(String, String)]) -> Result<()> {
         if let Some(gitlab_env_file) = env::var_os("GITLAB_ENV") {
-            let map = serde_json::to_value(version)?;
-            let map = map.as_object().unwrap();
+            append_to(&gitlab_env_file, &self.render(version, variables))?;
+        }
+        Ok(())
+    }
+}
+
+// Fields of the `release` keyword, as consumed by GitLab's release-cli
+#[derive(Serialize, Debug)]
+pub struct GitLabRelease {
+    pub tag_name: String,
+    pub name: String,
+    pub description: String,
+    #[serde(rename = "ref")]
+    pub reference: String,
+}
+
+impl From<&GitVersion> for GitLabRelease {
+    fn from(version: &GitVersion) -> Self {
+        Self {
+            tag_name: version.next_version_tag.clone(),
+            name: format!("Release {}", version.major_minor_patch),
+            description: format!(
+                "Release {} built from {} at {}",
+                version.major_minor_patch, version.branch_name, version.short_sha
+            ),
+            reference: version.sha.clone(),
+        }
+    }
+}
+
+pub fn export_gitlab_release(version: &GitVersion, path: &Path) -> Result<()> {
+    let release = GitLabRelease::from(version);
+    let content = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => serde_json::to_string_pretty(&release)? + "\n",
+        _ => serde_saphyr::to_string(&release)?,
+    };
+    fs::write(path, content)?;
+    Ok(())
+}
+
+// Lines for `docker build --build-arg` via xargs or a compose `env_file`
+pub fn export_docker_args(version: &GitVersion, path: &Path) -> Result<()> {
+    write_key_values(
+        path,
+        &[
+            ("GIT_VERSION_FULL_SEMVER", &version.full_sem_ver),
+            ("GIT_VERSION_SHA", &version.sha),
+            (
+                "GIT_VERSION_DOCKER_TAG",
+                &docker_tag_of(&version.full_sem_ver),
+            ),
+        ],
+    )
+}
+
+// Pre-defined annotation keys of the OCI image spec, where created is the time of the build
+pub fn export_docker_labels(version: &GitVersion, path: &Path) -> Result<()> {
+    let created = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    write_key_values(
+        path,
+        &[
+            ("org.opencontainers.image.version", &version.full_sem_ver),
+            ("org.opencontainers.image.revision", &version.sha),
+            ("org.opencontainers.image.created", &created),
+        ],
+    )
+}
+
+// Tags allow at most 128 of [A-Za-z0-9_.-], so e.g. the '+' of build metadata becomes '-'
+pub(crate) fn docker_tag_of(version: &str) -> String {
+    version
+        .chars()
+        .map(|c| match c {
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '_' | '.' | '-' => c,
+            _ => '-',
+        })
+        .take(128)
+        .collect()
+}
 
-            let mut file = OpenOptions::new()
-                .append(true)
-                .create(true)
-                .open(gitlab_env_file)?;
-
-            for (key, raw_value) in map {
-                let value = match raw_value {
-                    serde_json::Value::String(s) => s.clone(),
-                    _ => raw_value.to_string(),
-                };
-                writeln!(file, "GitVersion_{key}={value}")?;
+// Values are restricted to characters without meaning to a shell, so the lines need no quoting
+fn write_key_values(path: &Path, values: &[(&str, &String)]) -> Result<()> {
+    let mut content = String::new();
+    for (key, value) in values {
+        let value: String = value
+            .chars()
+            .map(|c| match c {
+                'A'..='Z' | 'a'..='z' | '0'..='9' | '_' | '.' | '-' | '+' | ':' | '/' => c,
+                _ => '-',
+            })
+            .collect();
+        content.push_str(&format!("{key}={value}\n"));
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+pub struct TeamCityExporter<'a> {
+    pub parameter_prefix: &'a str,
+    pub system_parameters: bool,
+}
+
+impl Exporter for TeamCityExporter<'_> {
+    // Service messages printed to stdout
+    fn render(&self, _version: &GitVersion, variables: &[(String, String)]) -> String {
+        let prefix = self.parameter_prefix;
+        let mut content = String::new();
+        for (key, value) in variables {
+            content.push_str(&format!(
+                "##teamcity[setParameter name='{prefix}.{key}' value='{value}']\n"
+            ));
+            if self.system_parameters {
+                content.push_str(&format!(
+                    "##teamcity[setParameter name='system.{prefix}.{key}' value='{value}']\n"
+                ));
             }
         }
+        content
+    }
+
+    fn export(&self, version: &GitVersion, variables: &[(String, String)]) -> Result<()> {
+        print!("{}", self.render(version, variables));
         Ok(())
     }
 }
 
-pub struct TeamCityExporter;
+pub struct BambooExporter;
 
-impl Exporter for TeamCityExporter {
-    fn export(&self, version: &GitVersion) -> Result<()> {
-        let map = serde_json::to_value(version)?;
-        let map = map.as_object().unwrap();
+impl Exporter for BambooExporter {
+    // Properties appended to $BAMBOO_INJECT_FILE
+    fn render(&self, _version: &GitVersion, variables: &[(String, String)]) -> String {
+        let mut content = String::new();
+        for (key, value) in variables {
+            content.push_str(&format!("GitVersion.{key}={}\n", escaped_property(value)));
+        }
+        content
+    }
 
-        for (key, raw_value) in map {
-            let value = match raw_value {
-                serde_json::Value::String(s) => s.clone(),
-                _ => raw_value.to_string(),
-            };
-            println!("##teamcity[setParameter name='GitVersion.{key}' value='{value}']");
-            println!("##teamcity[setParameter name='system.GitVersion.{key}' value='{value}']");
+    fn export(&self, version: &GitVersion, variables: &[(String, String)]) -> Result<()> {
+        if let Some(bamboo_inject_file) = env::var_os("BAMBOO_INJECT_FILE") {
+            append_to(&bamboo_inject_file, &self.render(version, variables))?;
+        }
+        Ok(())
+    }
+}
+
+// Escapes a value for Java properties files, as read by Bamboo's "Inject Variables" task
+fn escaped_property(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '=' => escaped.push_str("\\="),
+            ':' => escaped.push_str("\\:"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub struct GenericFileExporter<'a> {
+    pub output_env_file_var: &'a str,
+}
+
+impl Exporter for GenericFileExporter<'_> {
+    // Content appended to the file named by the configured variable
+    fn render(&self, _version: &GitVersion, variables: &[(String, String)]) -> String {
+        let mut content = String::new();
+        for (key, value) in variables {
+            content.push_str(&format!("GitVersion_{key}={value}\n"));
+        }
+        content
+    }
+
+    fn export(&self, version: &GitVersion, variables: &[(String, String)]) -> Result<()> {
+        if let Some(output_file) = env::var_os(self.output_env_file_var) {
+            append_to(&output_file, &self.render(version, variables))?;
         }
         Ok(())
     }
 }
 
-pub fn export_to_build_agent(version: &GitVersion) -> Result<()> {
+pub struct OctopusExporter;
+
+impl Exporter for OctopusExporter {
+    // Service messages printed to stdout
+    fn render(&self, _version: &GitVersion, variables: &[(String, String)]) -> String {
+        // Octopus expects all service message attributes to be base64 encoded
+        let mut content = String::new();
+        for (key, value) in variables {
+            content.push_str(&format!(
+                "##octopus[setVariable name='{}' value='{}']\n",
+                BASE64.encode(format!("GitVersion.{key}")),
+                BASE64.encode(value)
+            ));
+        }
+        content
+    }
+
+    fn export(&self, version: &GitVersion, variables: &[(String, String)]) -> Result<()> {
+        print!("{}", self.render(version, variables));
+        Ok(())
+    }
+}
+
+pub fn export_to_build_agent<T: Configuration>(version: &GitVersion, config: &T) -> Result<()> {
+    let variables = variables_of(version, config.export_variables())?;
+
+    // Runs independent of the detected build agent, e.g. for Harness or Semaphore
+    if !config.output_env_file_var().is_empty() {
+        GenericFileExporter {
+            output_env_file_var: config.output_env_file_var(),
+        }
+        .export(version, &variables)?;
+    }
+
+    // Octopus Deploy and Bamboo do not set CI, so they are detected beforehand
+    if env::vars_os().any(|(name, _)| name.to_string_lossy().starts_with("OCTOPUS_")) {
+        OctopusExporter.export(version, &variables)?;
+    }
+
+    if env::var_os("bamboo_buildKey").is_some() {
+        BambooExporter.export(version, &variables)?;
+    }
+
     if !env::var_os("CI")
         .is_some_and(|value| value.to_string_lossy().parse::<bool>().unwrap_or(false))
     {
         return Ok(());
     }
 
-    if env::var_os("GITHUB_ACTIONS").is_some() {
-        GitHubExporter.export(version)?;
+    // Gitea and Forgejo Actions provide a GitHub-compatible $GITHUB_OUTPUT
+    if env::var_os("GITHUB_ACTIONS").is_some() || env::var_os("GITEA_ACTIONS").is_some() {
+        GitHubExporter {
+            annotations: *config.annotations(),
+        }
+        .export(version, &variables)?;
     }
 
     if env::var_os("GITLAB_CI").is_some() {
-        GitLabExporter.export(version)?;
+        GitLabExporter.export(version, &variables)?;
     }
 
     if env::var_os("TEAMCITY_VERSION").is_some() {
-        TeamCityExporter.export(version)?;
+        TeamCityExporter {
+            parameter_prefix: config.teamcity_parameter_prefix(),
+            system_parameters: *config.teamcity_system_parameters(),
+        }
+        .export(version, &variables)?;
+    }
+
+    Ok(())
+}
+
+pub fn check_output_file_format(format: &str) -> Result<()> {
+    match format {
+        "json" | "yaml" | "dotenv" => Ok(()),
+        _ => Err(anyhow!(
+            r#"Invalid value "{}" for OutputFileFormat. Should be "json", "dotenv" or "yaml"."#,
+            format
+        )),
     }
+}
 
+pub fn export_to_file<T: Serialize>(output: &T, path: &Path, format: &str) -> Result<()> {
+    check_output_file_format(format)?;
+    let content = match format {
+        "json" => serde_json::to_string_pretty(output)? + "\n",
+        "yaml" => serde_saphyr::to_string(output)?,
+        "dotenv" => {
+            let map = serde_json::to_value(output)?;
+            let map = map.as_object().unwrap();
+
+            let mut content = String::new();
+            for (key, value) in flattened(map)
+                .into_iter()
+                .filter(|(key, _)| !NOT_EXPORTED_BY_DEFAULT.contains(&key.as_str()))
+            {
+                content.push_str(&format!("GitVersion_{key}={value}\n"));
+            }
+            content
+        }
+        _ => unreachable!("checked above"),
+    };
+    fs::write(path, content)?;
     Ok(())
 }
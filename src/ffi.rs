@@ -0,0 +1,94 @@
+use crate::config::{ConfigurationFile, DefaultConfig};
+use crate::{GitVersioner, panic_message};
+use anyhow::{Result, anyhow};
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Calculates the version of the repository at `path` and returns it as JSON.
+///
+/// `config_json` may be null or a JSON object with the fields of the configuration file
+/// (e.g. `{"PreReleaseTag": "alpha"}`). Returns null on failure, see `git_versioner_last_error`.
+///
+/// # Safety
+///
+/// `path` and `config_json` must be null or point to nul-terminated strings.
+/// The returned string must be released with `git_versioner_free`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn git_versioner_calculate(
+    path: *const c_char,
+    config_json: *const c_char,
+) -> *mut c_char {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| unsafe { calculate(path, config_json) }));
+    match result {
+        Ok(Ok(json)) => {
+            set_last_error(None);
+            json.into_raw()
+        }
+        Ok(Err(error)) => {
+            set_last_error(Some(format!("{error:#}")));
+            ptr::null_mut()
+        }
+        Err(payload) => {
+            set_last_error(Some(panic_message(payload)));
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Releases a string returned by `git_versioner_calculate`.
+///
+/// # Safety
+///
+/// `value` must be null or a pointer returned by `git_versioner_calculate` not freed before.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn git_versioner_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(unsafe { CString::from_raw(value) });
+    }
+}
+
+/// Returns the error of the last failed call on this thread, or null if it succeeded.
+///
+/// The string is owned by the library and valid until the next call on this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn git_versioner_last_error() -> *const c_char {
+    LAST_ERROR.with_borrow(|error| error.as_ref().map_or(ptr::null(), |error| error.as_ptr()))
+}
+
+unsafe fn calculate(path: *const c_char, config_json: *const c_char) -> Result<CString> {
+    let path =
+        unsafe { str_from(path, "Path") }?.ok_or_else(|| anyhow!("Path must not be null"))?;
+    let overrides = match unsafe { str_from(config_json, "Configuration") }? {
+        None => ConfigurationFile::default(),
+        Some(json) => serde_json::from_str(json)?,
+    };
+    let config = DefaultConfig::builder()
+        .path(path)
+        .overrides(overrides)
+        .build();
+
+    let version = GitVersioner::calculate_version(&config)?;
+    Ok(CString::new(serde_json::to_string(&version)?)?)
+}
+
+unsafe fn str_from<'a>(raw: *const c_char, name: &str) -> Result<Option<&'a str>> {
+    if raw.is_null() {
+        return Ok(None);
+    }
+    let value = unsafe { CStr::from_ptr(raw) }
+        .to_str()
+        .map_err(|error| anyhow!("{name} is not valid UTF-8: {error}"))?;
+    Ok(Some(value))
+}
+
+fn set_last_error(error: Option<String>) {
+    // Interior nul bytes cannot be represented, so they are replaced instead of losing the error
+    let error = error.map(|error| CString::new(error.replace('\0', " ")).unwrap());
+    LAST_ERROR.with_borrow_mut(|last_error| *last_error = error);
+}
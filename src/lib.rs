@@ -1,35 +1,206 @@
+pub mod apply;
 pub mod config;
 pub mod exporter;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "testkit")]
+pub mod testkit;
 
 use crate::config::{
-    Configuration, NO_BRANCH_NAME, PRERELEASE_WEIGHT_FEATURE, PRERELEASE_WEIGHT_MAIN,
-    PRERELEASE_WEIGHT_RELEASE, PRERELEASE_WEIGHT_TAG,
+    Configuration, FEATURE_BRANCH, NO_BRANCH_NAME, PRERELEASE_WEIGHT_FEATURE,
+    PRERELEASE_WEIGHT_MAIN, PRERELEASE_WEIGHT_RELEASE, PRERELEASE_WEIGHT_TAG, RELEASE_BRANCH,
+    canonicalize, without_verbatim_prefix,
 };
 use anyhow::{Result, anyhow};
-use chrono::DateTime;
 use chrono::offset::Utc;
+use chrono::{DateTime, Datelike, FixedOffset};
 use conventional_commit_parser::{commit::CommitType, parse};
 use git2::{Oid, Reference, Repository};
 use regex::Regex;
-use semver::{Comparator, Op, Prerelease, Version};
+use semver::{BuildMetadata, Comparator, Op, Prerelease, Version};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fmt::{Debug, Display};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 use std::time;
 
 const BRANCH_NAME_ID: &str = "BranchName";
 const VERSION_ID: &str = "Version";
+const CHANNEL_ID: &str = "Channel";
+const SNAPSHOT_PRE_RELEASE: &str = "SNAPSHOT";
+static EXPRESSION_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{([^{}]+)}").unwrap());
+static LEADING_VERSION_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\D*\d+(\.\d+){0,2}").unwrap());
 const IS_STABLE_VERSION: fn(&Version) -> bool = |version| version.pre.is_empty();
 const IS_RELEASE_VERSION: fn(&&VersionSource) -> bool = |source| IS_STABLE_VERSION(&source.version);
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum BranchType {
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BranchType {
     Trunk,            // Main development branch (trunk)
     Release(Version), // Release branch (e.g., release/1.0.0)
-    Other(String),    // Feature branch or any other branch type
+    Feature(String),  // Branch matching a feature branch pattern
+    Other(String),    // Any other branch type
 }
 
+impl Display for BranchType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BranchType::Trunk => write!(f, "trunk"),
+            BranchType::Release(version) => write!(f, "release({version})"),
+            BranchType::Feature(name) => write!(f, "feature({name})"),
+            BranchType::Other(name) => write!(f, "other({name})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PreReleaseWeight {
+    Main,
+    Release,
+    Tag,
+    Feature,
+}
+
+impl PreReleaseWeight {
+    fn value(self) -> u64 {
+        match self {
+            PreReleaseWeight::Main => PRERELEASE_WEIGHT_MAIN,
+            PreReleaseWeight::Release => PRERELEASE_WEIGHT_RELEASE,
+            PreReleaseWeight::Tag => PRERELEASE_WEIGHT_TAG,
+            PreReleaseWeight::Feature => PRERELEASE_WEIGHT_FEATURE,
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            PreReleaseWeight::Main => "Main",
+            PreReleaseWeight::Release => "Release",
+            PreReleaseWeight::Tag => "Tag",
+            PreReleaseWeight::Feature => "Feature",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum CommitMessageIncrementing {
+    Enabled,
+    Disabled,
+    MergeMessageOnly,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Scheme {
+    SemVer,
+    CalVer(CalVerFormat),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct CalVerFormat {
+    short_year: bool,
+}
+
+impl CalVerFormat {
+    fn parse(format: &str) -> Option<Self> {
+        match format.split('.').collect::<Vec<_>>()[..] {
+            [year @ ("YYYY" | "YY"), "MM" | "0M", "PATCH" | "MICRO"] => Some(Self {
+                short_year: year == "YY",
+            }),
+            _ => None,
+        }
+    }
+
+    fn major_minor_for(&self, date: &DateTime<FixedOffset>) -> (u64, u64) {
+        let year = date.year() as u64;
+        match self.short_year {
+            true => (year % 100, date.month() as u64),
+            false => (year, date.month() as u64),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum PreReleaseFormat {
+    Counted,
+    Dated,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum BumpScan {
+    All,
+    FirstParent,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum FeatureBranchMode {
+    PreRelease,
+    Metadata,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BranchCategory {
+    Main,
+    Release,
+    Feature,
+}
+
+impl BranchCategory {
+    const ALL: [BranchCategory; 3] = [Self::Main, Self::Release, Self::Feature];
+
+    fn parse(name: &str) -> Option<Self> {
+        match name.trim() {
+            "main" => Some(Self::Main),
+            "release" => Some(Self::Release),
+            "feature" => Some(Self::Feature),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Main => "main",
+            Self::Release => "release",
+            Self::Feature => "feature",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BuildMetadataMode {
+    None,
+    Commits,
+    Sha,
+    CommitsSha,
+}
+
+impl BuildMetadataMode {
+    fn metadata_for(&self, commits: u64, short_sha: &str) -> Option<String> {
+        match self {
+            BuildMetadataMode::None => None,
+            BuildMetadataMode::Commits => Some(commits.to_string()),
+            BuildMetadataMode::Sha => Some(format!("Sha.{short_sha}")),
+            BuildMetadataMode::CommitsSha => Some(format!("{commits}.Sha.{short_sha}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum TagSelection {
+    HighestVersion,
+    MostRecentCommit,
+}
+
+#[derive(Clone, Debug)]
+enum CommitDateSource {
+    CommitterUtc,
+    CommitterLocal,
+    Author,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum CommitBump {
     Major,
     Minor,
@@ -43,19 +214,70 @@ struct VersionSource {
     is_tag: bool,
 }
 
+#[derive(Clone, Debug)]
+struct BranchOverride {
+    pattern: Regex,
+    tag: Option<String>,
+    increment: Option<CommitBump>,
+}
+
 pub struct GitVersioner {
     repo: Repository,
+    config: GitVersionerConfig,
+    branch_override: Option<BranchOverride>,
+    replacements: HashMap<Oid, Oid>,
+}
+
+#[derive(Clone, Debug)]
+pub struct GitVersionerConfig {
+    path: PathBuf,
     trunk_pattern: Regex,
-    release_pattern: Regex,
-    feature_pattern: Regex,
+    release_patterns: Vec<Regex>,
+    feature_patterns: Vec<Regex>,
+    branch_precedence: Vec<BranchCategory>,
     version_pattern: Regex,
     prerelease_tag: String,
     patch_prerelease_tag: String,
+    trunk_prerelease_tag: String,
+    feature_label_prefix: String,
     continuous_delivery: bool,
-    is_commit_message_incrementing: bool,
+    loose_tag_versions: bool,
+    require_signed_tags: bool,
+    commit_message_incrementing: CommitMessageIncrementing,
+    bump_scan: BumpScan,
+    commit_date_source: CommitDateSource,
+    scheme: Scheme,
+    pre_release_format: PreReleaseFormat,
+    pre_release_number_padding: usize,
+    feature_branch_mode: FeatureBranchMode,
+    tag_selection: TagSelection,
+    include_remote_branches: bool,
+    as_release: bool,
+    no_metadata: bool,
+    fail_on_dirty: bool,
+    strict_semver: bool,
+    strict_sources: bool,
+    weight_base: Option<u64>,
+    snapshot: bool,
+    increment_on_first_commit: bool,
+    tag_message_channel: bool,
+    ignore_prerelease_tags: bool,
+    apply_replace_refs: bool,
+    verbose: bool,
+    preferred_tag_prefix: String,
+    build_metadata_format: String,
+    build_metadata_mode: BuildMetadataMode,
+    rebuild_counter_env: String,
+    sem_ver_format: String,
+    tag_message_template: String,
+    assembly_informational_format: String,
+    pr_label_bumps: Vec<(String, CommitBump)>,
+    branch_overrides: Vec<BranchOverride>,
+    head: Option<String>,
+    ignored_tag: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct GitVersion {
     pub major: u64,
@@ -68,9 +290,12 @@ pub struct GitVersion {
     pub pre_release_label_with_dash: String,
     pub pre_release_number: u64,
     pub weighted_pre_release_number: u64,
+    pub weight_source: String,
     pub build_metadata: String,
     pub full_build_meta_data: String,
     pub major_minor_patch: String,
+    pub next_version_tag: String,
+    pub tag_name: String,
     pub sem_ver: String,
     pub assembly_sem_ver: String,
     pub assembly_sem_file_ver: String,
@@ -81,8 +306,13 @@ pub struct GitVersion {
     pub sha: String,
     pub short_sha: String,
     pub version_source_sha: String,
+    pub version_source_kind: String,
     pub major_minor_patch_version_source_sha: String,
     pub commits_since_version_source: u64,
+    pub version_source_distance: u64,
+    pub commits_since_release_branch: i64,
+    pub describe: String,
+    pub channel: String,
     pub commit_date: String,
     pub commit_year: String,
     pub commit_month: String,
@@ -92,31 +322,806 @@ pub struct GitVersion {
     pub cal_ver_day: String,
     pub cal_ver_minor: u64,
     pub uncommitted_changes: u64,
+    pub repository_path: String,
+    pub git_dir: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct BranchVersion {
+    pub branch_name: String,
+    pub version: GitVersion,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct IncrementPreview {
+    pub current: String,
+    pub next_release: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct VersionComparison {
+    pub ref_a: String,
+    pub version_a: String,
+    pub ref_b: String,
+    pub version_b: String,
+    pub b_is_higher: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct DetectedVersionSource {
+    pub version: String,
+    pub short_sha: String,
+    pub is_tag: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ReleasePlan {
+    pub current: String,
+    pub next_release: String,
+    pub tag: String,
+    pub commit_count: u64,
+    pub commits: Vec<String>,
+    pub breaking_changes: bool,
+}
+
+impl Display for ReleasePlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Current version:  {}", self.current)?;
+        writeln!(f, "Next release:     {}", self.next_release)?;
+        writeln!(f, "Tag:              {}", self.tag)?;
+        writeln!(
+            f,
+            "Breaking changes: {}",
+            if self.breaking_changes { "yes" } else { "no" }
+        )?;
+        writeln!(f, "Commits:          {}", self.commit_count)?;
+        for commit in &self.commits {
+            writeln!(f, "  - {commit}")?;
+        }
+        Ok(())
+    }
+}
+
+// Everything besides the version itself that the output variables of a version are derived from
+struct VersionContext<'a> {
+    previous_pre_releases: Vec<String>,
+    branch_name: String,
+    source: &'a VersionSource,
+    major_minor_patch_source: Oid,
+    prerelease_weight: PreReleaseWeight,
+    weight_base: Option<u64>,
+    snapshot: bool,
+    pre_release_number_padding: usize,
+    head: Reference<'a>,
+    commit_date_time: DateTime<FixedOffset>,
+    cal_ver_date: DateTime<Utc>,
+    cal_ver_minor: u64,
+    commits_since_version_source: u64,
+    version_source_distance: u64,
+    commits_since_release_branch: i64,
+    uncommitted_changes: u64,
+    source_tag: Option<&'a str>,
+    channel: String,
+    preferred_tag_prefix: &'a str,
+    build_metadata_format: &'a str,
+    build_metadata_mode: BuildMetadataMode,
+    rebuild_counter: Option<u64>,
+    sem_ver_format: &'a str,
+    assembly_informational_format: &'a str,
 }
 
 struct FoundBranch {
+    name: String,
     branch_type: BranchType,
     distance: i64,
+    merge_base: Oid,
+}
+
+impl FoundBranch {
+    // Equally close branches prefer trunk over release branches, lower releases over higher ones
+    // and finally the name, so local and remote copies resolve the same way on every run
+    fn closeness(&self, other: &Self) -> std::cmp::Ordering {
+        self.distance
+            .cmp(&other.distance)
+            .then_with(|| self.branch_type.cmp(&other.branch_type))
+            .then_with(|| self.name.cmp(&other.name))
+    }
+}
+
+impl GitVersionerConfig {
+    pub fn new<T: Configuration>(config: &T) -> Result<Self> {
+        let case_insensitive = *config.case_insensitive_branches();
+        Ok(Self::with_patterns(
+            config,
+            Self::branch_regex(config.main_branch(), case_insensitive)?,
+            Self::branch_patterns_for(
+                config.release_branch(),
+                case_insensitive,
+                get_method_name(T::release_branch),
+                RELEASE_BRANCH,
+            )?,
+            Self::branch_patterns_for(
+                config.feature_branch(),
+                case_insensitive,
+                get_method_name(T::feature_branch),
+                FEATURE_BRANCH,
+            )?,
+            Self::version_pattern_for(config.tag_prefix())?,
+        ))
+    }
+
+    fn branch_regex(pattern: &str, case_insensitive: bool) -> Result<Regex, regex::Error> {
+        match case_insensitive {
+            true => Regex::new(&format!("(?i){pattern}")),
+            false => Regex::new(pattern),
+        }
+    }
+
+    fn branch_patterns_for(
+        patterns: &[String],
+        case_insensitive: bool,
+        option: &str,
+        example: &str,
+    ) -> Result<Vec<Regex>> {
+        patterns
+            .iter()
+            .map(|pattern| Self::branch_pattern_for(pattern, case_insensitive, option, example))
+            .collect()
+    }
+
+    fn branch_pattern_for(
+        pattern: &str,
+        case_insensitive: bool,
+        option: &str,
+        example: &str,
+    ) -> Result<Regex> {
+        let regex = Self::branch_regex(pattern, case_insensitive)?;
+        if !regex.capture_names().flatten().any(|n| n == BRANCH_NAME_ID) {
+            return Err(anyhow!(
+                r#"Pattern "{}" for {} is missing the named group "{}". Use a pattern like "{}"."#,
+                pattern,
+                stringcase::pascal_case(option),
+                BRANCH_NAME_ID,
+                example
+            ));
+        }
+        Ok(regex)
+    }
+
+    fn sem_ver_format_from(value: &str, option: &str) -> String {
+        let fields = match serde_json::to_value(GitVersion::default()) {
+            Ok(serde_json::Value::Object(fields)) => fields,
+            _ => serde_json::Map::new(),
+        };
+        for captures in EXPRESSION_PATTERN.captures_iter(value) {
+            let expression = &captures[1];
+            let name = expression
+                .split_once("??")
+                .map_or(expression, |(name, _)| name);
+            let name = name.trim();
+            if !name.starts_with("env:") && !fields.contains_key(name) {
+                panic!(
+                    r#"Invalid value "{}" for {}. Should only reference output variables like "{{Major}}", but "{}" is unknown."#,
+                    value,
+                    stringcase::pascal_case(option),
+                    name
+                );
+            }
+        }
+        value.to_string()
+    }
+
+    fn branch_precedence_from(value: &str, option: &str) -> Vec<BranchCategory> {
+        let mut precedence = Vec::new();
+        for name in value.split(',') {
+            let category = BranchCategory::parse(name).unwrap_or_else(|| {
+                panic!(
+                    r#"Invalid value "{}" for {}. Should be a comma-separated order of "main", "release" and "feature"."#,
+                    value,
+                    stringcase::pascal_case(option)
+                )
+            });
+            if !precedence.contains(&category) {
+                precedence.push(category);
+            }
+        }
+        // Categories left out keep their default order behind the configured ones
+        for category in BranchCategory::ALL {
+            if !precedence.contains(&category) {
+                precedence.push(category);
+            }
+        }
+        precedence
+    }
+
+    fn pr_label_bumps_from(values: &[String], option: &str) -> Vec<(String, CommitBump)> {
+        values
+            .iter()
+            .map(|value| {
+                let bump = match value.rsplit_once('=') {
+                    Some((label, "major")) if !label.is_empty() => (label, CommitBump::Major),
+                    Some((label, "minor")) if !label.is_empty() => (label, CommitBump::Minor),
+                    Some((label, "patch")) if !label.is_empty() => (label, CommitBump::Patch),
+                    _ => panic!(
+                        r#"Invalid value "{}" for {}. Should be "<label>=<major|minor|patch>"."#,
+                        value,
+                        stringcase::pascal_case(option)
+                    ),
+                };
+                (bump.0.to_string(), bump.1)
+            })
+            .collect()
+    }
+
+    fn branch_overrides_from(
+        values: &[String],
+        case_insensitive: bool,
+        option: &str,
+    ) -> Vec<BranchOverride> {
+        let invalid = |value: &str| -> ! {
+            panic!(
+                r#"Invalid value "{}" for {}. Should be "<pattern>:<key>=<value>[,<key>=<value>]" with keys "tag" and "increment" ("major", "minor" or "patch")."#,
+                value,
+                stringcase::pascal_case(option)
+            )
+        };
+        values
+            .iter()
+            .map(|value| {
+                let Some((pattern, overrides)) = value.rsplit_once(':') else {
+                    invalid(value)
+                };
+                let mut branch_override = BranchOverride {
+                    pattern: Self::branch_regex(pattern, case_insensitive)
+                        .unwrap_or_else(|_| invalid(value)),
+                    tag: None,
+                    increment: None,
+                };
+                for assignment in overrides.split(',') {
+                    match assignment.trim().split_once('=') {
+                        Some(("tag", tag)) if !tag.is_empty() => {
+                            branch_override.tag = Some(tag.to_string())
+                        }
+                        Some(("increment", "major")) => {
+                            branch_override.increment = Some(CommitBump::Major)
+                        }
+                        Some(("increment", "minor")) => {
+                            branch_override.increment = Some(CommitBump::Minor)
+                        }
+                        Some(("increment", "patch")) => {
+                            branch_override.increment = Some(CommitBump::Patch)
+                        }
+                        _ => invalid(value),
+                    }
+                }
+                branch_override
+            })
+            .collect()
+    }
+
+    pub fn with_patterns<T: Configuration>(
+        config: &T,
+        trunk_pattern: Regex,
+        release_patterns: Vec<Regex>,
+        feature_patterns: Vec<Regex>,
+        version_pattern: Regex,
+    ) -> Self {
+        Self {
+            path: config.path().clone(),
+            trunk_pattern,
+            release_patterns,
+            feature_patterns,
+            branch_precedence: Self::branch_precedence_from(
+                config.branch_precedence(),
+                get_method_name(T::branch_precedence),
+            ),
+            version_pattern,
+            prerelease_tag: config.pre_release_tag().to_string(),
+            patch_prerelease_tag: config.patch_pre_release_tag().to_string(),
+            trunk_prerelease_tag: config.trunk_pre_release_tag().to_string(),
+            feature_label_prefix: GitVersioner::escaped(config.feature_label_prefix()),
+            continuous_delivery: *config.continuous_delivery(),
+            loose_tag_versions: *config.loose_tag_versions(),
+            require_signed_tags: *config.require_signed_tags(),
+            commit_message_incrementing: match config.commit_message_incrementing() {
+                "Enabled" => CommitMessageIncrementing::Enabled,
+                "Disabled" => CommitMessageIncrementing::Disabled,
+                "MergeMessageOnly" => CommitMessageIncrementing::MergeMessageOnly,
+                v => panic!(
+                    r#"Invalid value "{}" for {}. Should be "Enabled", "Disabled" or "MergeMessageOnly"."#,
+                    v,
+                    stringcase::pascal_case(get_method_name(T::commit_message_incrementing))
+                ),
+            },
+            bump_scan: match config.bump_scan() {
+                "All" => BumpScan::All,
+                "FirstParent" => BumpScan::FirstParent,
+                v => panic!(
+                    r#"Invalid value "{}" for {}. Should be "All" or "FirstParent"."#,
+                    v,
+                    stringcase::pascal_case(get_method_name(T::bump_scan))
+                ),
+            },
+            commit_date_source: match config.commit_date_source() {
+                "CommitterUtc" => CommitDateSource::CommitterUtc,
+                "CommitterLocal" => CommitDateSource::CommitterLocal,
+                "Author" => CommitDateSource::Author,
+                v => panic!(
+                    r#"Invalid value "{}" for {}. Should be "CommitterUtc", "CommitterLocal" or "Author"."#,
+                    v,
+                    stringcase::pascal_case(get_method_name(T::commit_date_source))
+                ),
+            },
+            scheme: match config.scheme() {
+                "SemVer" => Scheme::SemVer,
+                "CalVer" => Scheme::CalVer(
+                    CalVerFormat::parse(config.cal_ver_format()).unwrap_or_else(|| {
+                        panic!(
+                            r#"Invalid value "{}" for {}. Should be "<YYYY|YY>.<MM|0M>.<PATCH|MICRO>"."#,
+                            config.cal_ver_format(),
+                            stringcase::pascal_case(get_method_name(T::cal_ver_format))
+                        )
+                    }),
+                ),
+                v => panic!(
+                    r#"Invalid value "{}" for {}. Should be "SemVer" or "CalVer"."#,
+                    v,
+                    stringcase::pascal_case(get_method_name(T::scheme))
+                ),
+            },
+            pre_release_format: match config.pre_release_format() {
+                "Counted" => PreReleaseFormat::Counted,
+                "Dated" => PreReleaseFormat::Dated,
+                v => panic!(
+                    r#"Invalid value "{}" for {}. Should be "Counted" or "Dated"."#,
+                    v,
+                    stringcase::pascal_case(get_method_name(T::pre_release_format))
+                ),
+            },
+            pre_release_number_padding: match config.pre_release_format() {
+                "Dated" if *config.pre_release_number_padding() > 0 => panic!(
+                    r#"Invalid value "{}" for {}. Should be 0 with PreReleaseFormat "Dated"."#,
+                    config.pre_release_number_padding(),
+                    stringcase::pascal_case(get_method_name(T::pre_release_number_padding))
+                ),
+                _ => *config.pre_release_number_padding() as usize,
+            },
+            feature_branch_mode: match config.feature_branch_mode() {
+                "PreRelease" => FeatureBranchMode::PreRelease,
+                "Metadata" => FeatureBranchMode::Metadata,
+                v => panic!(
+                    r#"Invalid value "{}" for {}. Should be "PreRelease" or "Metadata"."#,
+                    v,
+                    stringcase::pascal_case(get_method_name(T::feature_branch_mode))
+                ),
+            },
+            tag_selection: match config.tag_selection() {
+                "highest-version" => TagSelection::HighestVersion,
+                "most-recent-commit" => TagSelection::MostRecentCommit,
+                v => panic!(
+                    r#"Invalid value "{}" for {}. Should be "highest-version" or "most-recent-commit"."#,
+                    v,
+                    stringcase::pascal_case(get_method_name(T::tag_selection))
+                ),
+            },
+            include_remote_branches: *config.include_remote_branches(),
+            as_release: *config.as_release(),
+            no_metadata: *config.no_metadata(),
+            fail_on_dirty: *config.fail_on_dirty(),
+            strict_semver: *config.strict_semver(),
+            strict_sources: *config.strict_sources(),
+            weight_base: *config.weight_base(),
+            snapshot: *config.snapshot(),
+            increment_on_first_commit: *config.increment_on_first_commit(),
+            tag_message_channel: *config.tag_message_channel(),
+            ignore_prerelease_tags: *config.ignore_prerelease_tags(),
+            apply_replace_refs: *config.apply_replace_refs(),
+            verbose: *config.verbose(),
+            preferred_tag_prefix: config.preferred_tag_prefix().to_string(),
+            build_metadata_format: config.build_metadata_format().to_string(),
+            build_metadata_mode: match config.build_metadata_mode() {
+                "none" => BuildMetadataMode::None,
+                "commits" => BuildMetadataMode::Commits,
+                "sha" => BuildMetadataMode::Sha,
+                "commits.sha" => BuildMetadataMode::CommitsSha,
+                v => panic!(
+                    r#"Invalid value "{}" for {}. Should be "none", "commits", "sha" or "commits.sha"."#,
+                    v,
+                    stringcase::pascal_case(get_method_name(T::build_metadata_mode))
+                ),
+            },
+            rebuild_counter_env: config.rebuild_counter_env().to_string(),
+            sem_ver_format: Self::sem_ver_format_from(
+                config.sem_ver_format(),
+                get_method_name(T::sem_ver_format),
+            ),
+            tag_message_template: Self::sem_ver_format_from(
+                config.tag_message_template(),
+                get_method_name(T::tag_message_template),
+            ),
+            assembly_informational_format: config.assembly_informational_format().to_string(),
+            pr_label_bumps: Self::pr_label_bumps_from(
+                config.pr_label_bumps(),
+                get_method_name(T::pr_label_bumps),
+            ),
+            branch_overrides: Self::branch_overrides_from(
+                config.branch_config(),
+                *config.case_insensitive_branches(),
+                get_method_name(T::branch_config),
+            ),
+            head: None,
+            ignored_tag: None,
+        }
+    }
+
+    pub fn version_pattern_for(tag_prefix: &str) -> Result<Regex, regex::Error> {
+        // The version must start with a number, so tags like "release-notes" never qualify
+        Regex::new(&format!(
+            r"^(?:{tag_prefix})(?<Version>\d+(?:\.\d+)*(?:[-+].+)?)$"
+        ))
+    }
+
+    pub fn with_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    // Calculates the version as if the given reference was checked out
+    pub fn with_head<S: Into<String>>(mut self, reference: S) -> Self {
+        self.head = Some(reference.into());
+        self
+    }
+
+    pub fn without_tag<S: Into<String>>(mut self, name: S) -> Self {
+        self.ignored_tag = Some(name.into());
+        self
+    }
+
+    pub fn with_as_release(mut self, as_release: bool) -> Self {
+        self.as_release = as_release;
+        self
+    }
 }
 
 impl GitVersioner {
     pub fn calculate_version<T: Configuration>(config: &T) -> Result<GitVersion> {
-        let versioner = Self::new(config)?;
+        Self::calculate_version_with(&GitVersionerConfig::new(config)?)
+    }
 
-        let head = versioner.head()?;
-        let branch_name = Self::branch_name_for(&head)?;
+    pub fn increment_preview<T: Configuration>(config: &T) -> Result<IncrementPreview> {
+        let config = GitVersionerConfig::new(config)?;
+        let current = Self::calculate_version_with(&config)?;
+        let next_release = Self::calculate_version_with(&config.with_as_release(true))?;
+
+        Ok(IncrementPreview {
+            current: current.full_sem_ver,
+            next_release: next_release.full_sem_ver,
+        })
+    }
+
+    // Calculates the versions as if each reference was checked out, comparing them by semver
+    // precedence, so build metadata does not make a version higher
+    pub fn compare<T: Configuration>(
+        config: &T,
+        ref_a: &str,
+        ref_b: &str,
+    ) -> Result<VersionComparison> {
+        let config = GitVersionerConfig::new(config)?;
+        let repo = Self::open_repository(&config.path)?;
+
+        let version_at = |reference: &str| -> Result<String> {
+            let name = match repo.resolve_reference_from_short_name(reference) {
+                Ok(resolved) => resolved.name().unwrap_or(reference).to_string(),
+                Err(_) => return Err(anyhow!(r#"Reference "{}" does not exist"#, reference)),
+            };
+            Ok(Self::calculate_version_with(&config.clone().with_head(name))?.full_sem_ver)
+        };
+        let version_a = version_at(ref_a)?;
+        let version_b = version_at(ref_b)?;
+
+        let b_is_higher = Version::parse(&version_b)?.cmp_precedence(&Version::parse(&version_a)?)
+            == std::cmp::Ordering::Greater;
+        Ok(VersionComparison {
+            ref_a: ref_a.to_string(),
+            version_a,
+            ref_b: ref_b.to_string(),
+            version_b,
+            b_is_higher,
+        })
+    }
+
+    pub fn version_sources<T: Configuration>(config: &T) -> Result<Vec<DetectedVersionSource>> {
+        let config = GitVersionerConfig::new(config)?;
+        let versioner = Self::new(&config)?;
+
+        let mut sources = versioner
+            .version_tags_matching(&|_: &Version| true)?
+            .into_iter()
+            .chain(versioner.version_branches()?)
+            .chain(match config.include_remote_branches {
+                true => versioner.remote_version_branches()?,
+                false => HashSet::new(),
+            })
+            .collect::<Vec<_>>();
+        sources.sort_by(|a, b| {
+            a.version
+                .cmp(&b.version)
+                .then_with(|| a.is_tag.cmp(&b.is_tag))
+                .then_with(|| a.commit_id.cmp(&b.commit_id))
+        });
+
+        Ok(sources
+            .into_iter()
+            .map(|source| DetectedVersionSource {
+                version: source.version.to_string(),
+                short_sha: source.commit_id.to_string()[..7].to_string(),
+                is_tag: source.is_tag,
+            })
+            .collect())
+    }
+
+    pub fn calculate_all_branches<T: Configuration>(config: &T) -> Result<Vec<BranchVersion>> {
+        let config = GitVersionerConfig::new(config)?;
+        let repo = Self::open_repository(&config.path)?;
+
+        let mut branch_names = Vec::new();
+        for branch in repo.branches(Some(git2::BranchType::Local))? {
+            let (branch, _) = branch?;
+            if let Ok(Some(name)) = branch.name() {
+                branch_names.push(name.to_string());
+            }
+        }
+        branch_names.sort();
+
+        branch_names
+            .into_iter()
+            .map(|branch_name| {
+                let version = Self::calculate_version_with(
+                    &config
+                        .clone()
+                        .with_head(format!("refs/heads/{branch_name}")),
+                )?;
+                Ok(BranchVersion {
+                    branch_name,
+                    version,
+                })
+            })
+            .collect()
+    }
+
+    pub fn release_plan<T: Configuration>(config: &T) -> Result<ReleasePlan> {
+        let config = GitVersionerConfig::new(config)?;
+        let versioner = Self::new(&config)?;
+        let current = Self::calculate_version_with(&config)?;
+        let next_release = Self::calculate_version_with(&config.with_as_release(true))?;
+
+        let head_id = versioner.head()?.peel_to_commit()?.id();
+        let source_id = Oid::from_str(&current.version_source_sha).unwrap_or(Oid::ZERO_SHA1);
+        let commits = versioner.commit_subjects_between(head_id, source_id)?;
+        let breaking_changes = matches!(
+            versioner.determine_bump_between(head_id, source_id)?,
+            CommitBump::Major
+        );
+
+        Ok(ReleasePlan {
+            current: current.full_sem_ver,
+            next_release: next_release.full_sem_ver,
+            tag: next_release.next_version_tag,
+            commit_count: commits.len() as u64,
+            commits,
+            breaking_changes,
+        })
+    }
+
+    pub fn verify_tag<T: Configuration>(config: &T, tag: &str) -> Result<Version> {
+        let tag_prefix = config.tag_prefix();
+        let config = GitVersionerConfig::new(config)?;
+        let versioner = Self::new(&config)?;
+        let reference = format!("refs/tags/{tag}");
+        let commit_id = match versioner.repo.find_reference(&reference) {
+            Ok(reference) => reference.peel_to_commit()?.id(),
+            Err(_) => return Err(anyhow!(r#"Tag "{}" does not exist"#, tag)),
+        };
+
+        let Some(tagged) = versioner.version_matching_in(tag, &|_: &Version| true) else {
+            return Err(anyhow!(
+                r#"Tag "{}" does not match the tag prefix "{}" followed by a version"#,
+                tag,
+                tag_prefix
+            ));
+        };
+
+        let on_allowed_branch = versioner
+            .find_all_source_branches(commit_id)?
+            .iter()
+            .any(|found| found.distance == 0);
+        if !on_allowed_branch {
+            return Err(anyhow!(
+                r#"Tag "{}" is on commit {}, which is on neither the main branch nor a release branch"#,
+                tag,
+                &commit_id.to_string()[..7]
+            ));
+        }
+
+        // The tag itself must not serve as version source of the version it is checked against
+        let expected = Self::calculate_version_with(
+            &config
+                .with_head(reference)
+                .with_as_release(true)
+                .without_tag(tag),
+        )?;
+        let expected = Version::parse(&expected.sem_ver)?;
+        if tagged != expected {
+            return Err(anyhow!(
+                r#"Tag "{}" states version {}, but its commit yields version {}"#,
+                tag,
+                tagged,
+                expected
+            ));
+        }
+        Ok(expected)
+    }
+
+    pub fn create_tag<T: Configuration>(config: &T) -> Result<GitVersion> {
+        let config = GitVersionerConfig::new(config)?;
+        let version = Self::calculate_version_with(&config)?;
+        let versioner = Self::new(&config)?;
+        let head = versioner.head()?.peel_to_commit()?;
+
+        let message = version.format(&config.tag_message_template);
+        let signature = versioner.repo.signature()?;
+        match versioner.repo.tag(
+            &version.tag_name,
+            head.as_object(),
+            &signature,
+            &message,
+            false,
+        ) {
+            Ok(_) => Ok(version),
+            Err(error) if error.code() == git2::ErrorCode::Exists => {
+                Err(anyhow!(r#"Tag "{}" already exists"#, version.tag_name))
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn graph<T: Configuration>(config: &T) -> Result<String> {
+        let repo = Self::open_repository(config.path())?;
+        let head = repo.head().ok();
+        let head_name = head
+            .as_ref()
+            .filter(|head| head.is_branch())
+            .and_then(|head| head.name().ok());
+
+        let mut decorations: HashMap<Oid, Vec<String>> = HashMap::new();
+        for reference in repo.references()? {
+            let reference = reference?;
+            if !(reference.is_branch() || reference.is_remote() || reference.is_tag())
+                || matches!(reference.symbolic_target(), Ok(Some(_)))
+            {
+                continue; // e.g. notes, stashes or remote HEADs
+            }
+            let (Ok(name), Ok(commit)) = (reference.shorthand(), reference.peel_to_commit()) else {
+                continue;
+            };
+            let names = decorations.entry(commit.id()).or_default();
+            match (reference.is_tag(), head_name == reference.name().ok()) {
+                (true, _) => names.push(format!("tag: {name}")),
+                (false, true) => names.insert(0, format!("HEAD -> {name}")),
+                (false, false) => names.push(name.to_string()),
+            }
+        }
+        if head_name.is_none()
+            && let Some(Ok(commit)) = head.as_ref().map(|head| head.peel_to_commit())
+        {
+            decorations
+                .entry(commit.id())
+                .or_default()
+                .insert(0, "HEAD".to_string()); // Detached HEAD
+        }
+
+        let mut revision_walk = repo.revwalk()?;
+        revision_walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+        for id in decorations.keys() {
+            revision_walk.push(*id)?;
+        }
+        let mut graph = String::new();
+        for id in revision_walk {
+            let commit = repo.find_commit(id?)?;
+            let short_id = &commit.id().to_string()[..7];
+            let summary = commit.summary().ok().flatten().unwrap_or_default();
+            match decorations.get(&commit.id()) {
+                Some(names) => {
+                    graph.push_str(&format!("* {short_id} ({}) {summary}\n", names.join(", ")))
+                }
+                None => graph.push_str(&format!("* {short_id} {summary}\n")),
+            }
+        }
+        Ok(graph)
+    }
+
+    pub fn calculate_version_with(config: &GitVersionerConfig) -> Result<GitVersion> {
+        let mut versioner = Self::new(config)?;
+
+        let uncommitted_changes = versioner.uncommitted_changes()?;
+        if config.fail_on_dirty && uncommitted_changes > 0 {
+            return Err(anyhow!(
+                "Working tree has {} uncommitted change(s). Commit or stash them before building a release, or omit --fail-on-dirty.",
+                uncommitted_changes
+            ));
+        }
+
+        // Finding conflicts walks all tags, so it is only done if they are reported
+        if config.strict_sources || config.verbose {
+            for conflict in versioner.conflicting_version_tags()? {
+                if config.strict_sources {
+                    return Err(anyhow!(
+                        "{conflict}. Remove all but one of them or omit --strict-sources."
+                    ));
+                }
+                eprintln!("Warning: {conflict}, the version source is either of them");
+            }
+        }
+        if config.verbose {
+            for skipped in versioner.skipped_version_tags()? {
+                eprintln!("{skipped}");
+            }
+        }
+
+        let branch_name = versioner.branch_name_for(&versioner.head()?)?;
         let branch_type_at_head = versioner.determine_branch_type_by_name(&branch_name);
+        let branch_override = config
+            .branch_overrides
+            .iter()
+            .find(|branch_override| branch_override.pattern.is_match(&branch_name));
+        versioner.branch_override = branch_override.cloned();
+
+        let head = versioner.head()?;
+        let head_id = head.peel_to_commit()?.id();
 
-        let (mut version, source, major_minor_patch_source, mut prerelease_weight) =
-            match branch_type_at_head {
+        let (mut version, source, major_minor_patch_source, mut prerelease_weight) = match versioner
+            .release_tag_at(head_id)?
+        {
+            Some(source) => Ok(Self::version_from(&source, PreReleaseWeight::Tag)),
+            None => match branch_type_at_head {
                 BranchType::Trunk => versioner.calculate_version_for_trunk(),
                 BranchType::Release(version) => versioner.calculate_version_for_release(&version),
-                BranchType::Other(name) => versioner.calculate_version_for_feature(&name),
-            }?;
+                BranchType::Feature(name) | BranchType::Other(name) => {
+                    versioner.calculate_version_for_feature(&name)
+                }
+            },
+        }?;
+
+        if config.require_signed_tags {
+            for source in [&source, &major_minor_patch_source] {
+                versioner.verify_signed_tag_for(source)?;
+            }
+        }
+
+        let source_tag = versioner.tag_names_for(&source)?.into_iter().min();
+        let message_channel = match config.tag_message_channel {
+            true => source_tag
+                .as_deref()
+                .and_then(|tag| versioner.tag_message_channel_for(tag)),
+            false => None,
+        };
+        // At the tag itself, e.g. "channel: rc" turns the release into a pre-release
+        if source.commit_id == head_id
+            && version.pre.is_empty()
+            && let Some(pre) = message_channel
+                .as_deref()
+                .and_then(|channel| Prerelease::new(channel).ok())
+        {
+            version.pre = pre;
+        }
 
-        if *config.as_release() {
+        if config.as_release {
             version.pre = Prerelease::EMPTY;
-            prerelease_weight = PRERELEASE_WEIGHT_TAG;
+            prerelease_weight = PreReleaseWeight::Tag;
+        }
+
+        if config.no_metadata {
+            version.build = BuildMetadata::EMPTY;
         }
 
         let head_commit = head.peel_to_commit()?;
@@ -129,49 +1134,160 @@ impl GitVersioner {
             head_commit.id(),
         )?;
         let previous_pre_releases = versioner.previous_pre_releases_for(&version)?;
-
-        Ok(GitVersion::new(
+        let commit_date_time = versioner.commit_date_time_from_source(&head_commit);
+        let commits_since_release_branch = versioner.commits_since_release_branch(head_id)?;
+        let commits_since_version_source =
+            versioner.count_commits_between(head_id, source.commit_id)? as u64;
+        // Differs from the commits since the version source if it is not an ancestor of HEAD
+        let version_source_distance = versioner
+            .count_commits_between(head_id, versioner.merge_base(head_id, source.commit_id)?)?
+            as u64;
+        let channel = source_tag
+            .as_deref()
+            .and_then(|tag| config.version_pattern.captures(tag))
+            .and_then(|captures| captures.name(CHANNEL_ID))
+            .map(|channel| channel.as_str().to_string())
+            .or(message_channel)
+            .unwrap_or_default();
+
+        let mut result = GitVersion::new(
             version,
-            previous_pre_releases,
-            branch_name,
-            source.commit_id,
-            major_minor_patch_source.commit_id,
-            prerelease_weight,
-            head,
-            cal_ver_date,
-            cal_ver_minor,
-            config.assembly_informational_format(),
-        ))
+            VersionContext {
+                previous_pre_releases,
+                branch_name,
+                source: &source,
+                major_minor_patch_source: major_minor_patch_source.commit_id,
+                prerelease_weight,
+                weight_base: config.weight_base,
+                snapshot: config.snapshot,
+                pre_release_number_padding: config.pre_release_number_padding,
+                head,
+                commit_date_time,
+                cal_ver_date,
+                cal_ver_minor,
+                commits_since_version_source,
+                version_source_distance,
+                commits_since_release_branch,
+                uncommitted_changes,
+                source_tag: source_tag.as_deref(),
+                channel,
+                preferred_tag_prefix: &config.preferred_tag_prefix,
+                build_metadata_format: match config.no_metadata {
+                    true => "",
+                    false => &config.build_metadata_format,
+                },
+                build_metadata_mode: match config.no_metadata {
+                    true => BuildMetadataMode::None,
+                    false => config.build_metadata_mode,
+                },
+                rebuild_counter: match config.no_metadata {
+                    true => None,
+                    false => Self::rebuild_counter_from(&config.rebuild_counter_env),
+                },
+                sem_ver_format: &config.sem_ver_format,
+                assembly_informational_format: &config.assembly_informational_format,
+            },
+        );
+        result.repository_path = versioner
+            .repo
+            .workdir()
+            .map(Self::normalized_path)
+            .unwrap_or_default();
+        result.git_dir = Self::normalized_path(versioner.repo.path());
+
+        if config.strict_semver {
+            Self::verify_assembly_versions(&result)?;
+        }
+        Ok(result)
     }
 
-    fn new<T: Configuration>(config: &T) -> Result<GitVersioner> {
-        let versioner = Self {
-            repo: Repository::open(config.path())?,
-            trunk_pattern: Regex::new(config.main_branch())?,
-            release_pattern: Regex::new(config.release_branch())?,
-            feature_pattern: Regex::new(config.feature_branch())?,
-            version_pattern: Regex::new(&format!("^{}(?<Version>.+)", config.tag_prefix()))?,
-            prerelease_tag: config.pre_release_tag().to_string(),
-            patch_prerelease_tag: config.patch_pre_release_tag().to_string(),
-            continuous_delivery: *config.continuous_delivery(),
-            is_commit_message_incrementing: match config.commit_message_incrementing() {
-                "Enabled" => true,
-                "Disabled" => false,
-                v => panic!(
-                    r#"Invalid value "{}" for {}. Should be "Enabled" or "Disabled"."#,
-                    v,
-                    stringcase::pascal_case(get_method_name(T::commit_message_incrementing))
-                ),
-            },
+    fn verify_assembly_versions(version: &GitVersion) -> Result<()> {
+        // .NET assembly versions consist of four components of at most 65535 each
+        for (name, assembly_version) in [
+            ("AssemblySemVer", &version.assembly_sem_ver),
+            ("AssemblySemFileVer", &version.assembly_sem_file_ver),
+        ] {
+            if assembly_version
+                .split('.')
+                .any(|component| component.parse::<u16>().is_err())
+            {
+                return Err(anyhow!(
+                    "{} {} exceeds the maximum component value of 65535 of assembly versions",
+                    name,
+                    assembly_version
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn new(config: &GitVersionerConfig) -> Result<GitVersioner> {
+        let repo = Self::open_repository(&config.path)?;
+        let replacements = match config.apply_replace_refs {
+            true => Self::replacements_in(&repo)?,
+            false => HashMap::new(),
         };
-        Ok(versioner)
+        Ok(Self {
+            repo,
+            config: config.clone(),
+            branch_override: None,
+            replacements,
+        })
+    }
+
+    // Maps each replaced commit to its replacement, as created by e.g. "git replace --graft"
+    fn replacements_in(repo: &Repository) -> Result<HashMap<Oid, Oid>> {
+        let mut replacements = HashMap::new();
+        for reference in repo.references_glob("refs/replace/*")? {
+            let reference = reference?;
+            let original = reference
+                .name()
+                .ok()
+                .and_then(|name| name.strip_prefix("refs/replace/"))
+                .and_then(|sha| Oid::from_str(sha).ok());
+            if let (Some(original), Some(replacement)) = (original, reference.target()) {
+                replacements.insert(original, replacement);
+            }
+        }
+        Ok(replacements)
+    }
+
+    fn rebuild_counter_from(env_name: &str) -> Option<u64> {
+        if env_name.is_empty() {
+            return None;
+        }
+        env::var(env_name)
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+            .filter(|counter| *counter > 1)
+    }
+
+    fn normalized_path(path: &Path) -> String {
+        canonicalize(path)
+            .unwrap_or_else(|_| without_verbatim_prefix(path))
+            .display()
+            .to_string()
+    }
+
+    fn open_repository(path: &Path) -> Result<Repository, git2::Error> {
+        let start = match path.is_file() {
+            true => path.parent().unwrap_or(path),
+            false => path,
+        };
+        Repository::discover(start)
     }
 
     fn head(&self) -> Result<Reference<'_>, git2::Error> {
-        self.repo.head()
+        match &self.config.head {
+            Some(reference) => self.repo.find_reference(reference),
+            None => self.repo.head(),
+        }
     }
 
-    fn branch_name_for(reference: &Reference) -> Result<String> {
+    fn branch_name_for(&self, reference: &Reference) -> Result<String> {
+        let reference = self.resolved(reference)?;
         if !reference.is_branch() {
             return Ok(NO_BRANCH_NAME.to_string());
         }
@@ -182,26 +1298,90 @@ impl GitVersioner {
         }
     }
 
-    fn determine_branch_type_by_name(&self, name: &str) -> BranchType {
-        if self.trunk_pattern.is_match(name) {
-            return BranchType::Trunk;
+    fn resolved(&self, reference: &Reference) -> Result<Reference<'_>> {
+        const MAX_SYMBOLIC_DEPTH: usize = 10;
+
+        let mut name = reference
+            .name()
+            .map_err(|_| anyhow!("Name for reference could not be determined"))?
+            .to_string();
+        for _ in 0..MAX_SYMBOLIC_DEPTH {
+            let current = self.repo.find_reference(&name)?;
+            match current.symbolic_target()? {
+                Some(target) => name = target.to_string(),
+                None => return Ok(current),
+            }
         }
 
-        if let Some(captures) = self.release_pattern.captures(name)
-            && let Some(branch_name) = captures.name(BRANCH_NAME_ID)
-            && let Some(version) =
-                self.version_matching_in(Self::loose(branch_name.as_str()), &IS_STABLE_VERSION)
-        {
-            return BranchType::Release(version);
-        }
+        Err(anyhow!("Symbolic reference {name} is nested too deeply"))
+    }
+
+    pub fn branch_type(&self, name: &str) -> BranchType {
+        self.determine_branch_type_by_name(name)
+    }
+
+    fn determine_branch_type_by_name(&self, name: &str) -> BranchType {
+        let mut matches = self
+            .config
+            .branch_precedence
+            .iter()
+            .filter_map(|category| Some((category, self.branch_type_for(category, name)?)));
 
-        if let Some(captures) = self.feature_pattern.captures(name)
-            && let Some(branch_name) = captures.name(BRANCH_NAME_ID)
+        let Some((category, branch_type)) = matches.next() else {
+            return BranchType::Other(name.to_string());
+        };
+        if self.config.verbose
+            && let Some((other, _)) = matches.next()
         {
-            return BranchType::Other(branch_name.as_str().to_string());
+            eprintln!(
+                "Branch {} matches both the {} and the {} pattern, classified as {} branch",
+                name,
+                category.name(),
+                other.name(),
+                category.name()
+            );
         }
+        branch_type
+    }
 
-        BranchType::Other(name.to_string())
+    fn branch_type_for(&self, category: &BranchCategory, name: &str) -> Option<BranchType> {
+        match category {
+            BranchCategory::Main => self
+                .config
+                .trunk_pattern
+                .is_match(name)
+                .then_some(BranchType::Trunk),
+            BranchCategory::Release => {
+                self.config
+                    .release_patterns
+                    .iter()
+                    .find_map(|release_pattern| {
+                        let captures = release_pattern.captures(name)?;
+                        let branch_name = captures.name(BRANCH_NAME_ID)?.as_str();
+                        // Names with trailing text, e.g. "1.0.0/rc", use their leading version
+                        let version_name = match self
+                            .version_matching_in(Self::loose(branch_name), &|_: &Version| true)
+                        {
+                            Some(_) => branch_name,
+                            None => LEADING_VERSION_PATTERN
+                                .find(branch_name)
+                                .map_or(branch_name, |leading| leading.as_str()),
+                        };
+                        self.version_matching_in(Self::loose(version_name), &IS_STABLE_VERSION)
+                            .map(BranchType::Release)
+                    })
+            }
+            BranchCategory::Feature => {
+                self.config
+                    .feature_patterns
+                    .iter()
+                    .find_map(|feature_pattern| {
+                        let captures = feature_pattern.captures(name)?;
+                        let branch_name = captures.name(BRANCH_NAME_ID)?;
+                        Some(BranchType::Feature(branch_name.as_str().to_string()))
+                    })
+            }
+        }
     }
 
     fn escaped(name: &str) -> String {
@@ -209,6 +1389,34 @@ impl GitVersioner {
         name.replace(|c: char| !c.is_alphanumeric(), ESCAPE_CHARACTER)
     }
 
+    fn pre_release_label_for(&self, name: &str) -> Result<String> {
+        const FALLBACK_LABEL: &str = "branch";
+        const COLLISION_SUFFIX: &str = "feature";
+        if let Some(tag) = self.branch_override_tag() {
+            return Ok(tag.to_string());
+        }
+        let escaped = Self::escaped(name);
+        let label = match escaped.chars().any(|c| c != '-') {
+            true => escaped,
+            false => {
+                let hash = Oid::hash_object(git2::ObjectType::Blob, name.as_bytes())?.to_string();
+                format!("{FALLBACK_LABEL}-{}", &hash[..7])
+            }
+        };
+        let label = format!("{}{label}", self.config.feature_label_prefix);
+        if [
+            &self.config.prerelease_tag,
+            &self.config.patch_prerelease_tag,
+            &self.config.trunk_prerelease_tag,
+        ]
+        .contains(&&label)
+        {
+            // Keeps e.g. feature/pre apart from trunk pre-releases counted in continuous delivery
+            return Ok(format!("{label}-{COLLISION_SUFFIX}"));
+        }
+        Ok(label)
+    }
+
     fn version_tags_matching<F>(&self, condition: &F) -> Result<HashSet<VersionSource>>
     where
         F: Fn(&Version) -> bool,
@@ -217,7 +1425,11 @@ impl GitVersioner {
         let tag_names = self.repo.tag_names(None)?;
         for tag_name in tag_names.iter().flatten() {
             if let Some(tag_name) = tag_name
-                && let Some(version) = self.version_matching_in(tag_name, condition)
+                && self.config.ignored_tag.as_deref() != Some(tag_name)
+                && let Some(version) = self.version_matching_in(tag_name, &|version| {
+                    condition(version)
+                        && (version.pre.is_empty() || !self.config.ignore_prerelease_tags)
+                })
                 && let Some(commit_id) = self.tag_id_for(tag_name)
             {
                 version_tags.insert(VersionSource {
@@ -228,16 +1440,103 @@ impl GitVersioner {
             }
         }
 
-        Ok(version_tags)
+        Ok(version_tags)
+    }
+
+    // Tags stating the same version on different commits, e.g. "1.2.0" and "v1.2.0" created by
+    // different tools, make the version source depend on which of them is picked
+    fn conflicting_version_tags(&self) -> Result<Vec<String>> {
+        let mut tags_by_version: HashMap<Version, Vec<(String, Oid)>> = HashMap::new();
+        let tag_names = self.repo.tag_names(None)?;
+        for tag_name in tag_names.iter().flatten() {
+            if let Some(tag_name) = tag_name
+                && self.config.ignored_tag.as_deref() != Some(tag_name)
+                && let Some(version) = self.version_matching_in(tag_name, &|version| {
+                    version.pre.is_empty() || !self.config.ignore_prerelease_tags
+                })
+                && let Some(commit_id) = self.tag_id_for(tag_name)
+            {
+                tags_by_version
+                    .entry(version)
+                    .or_default()
+                    .push((tag_name.to_string(), commit_id));
+            }
+        }
+
+        let mut conflicts = tags_by_version
+            .into_iter()
+            .filter(|(_, tags)| tags.iter().any(|(_, commit_id)| *commit_id != tags[0].1))
+            .collect::<Vec<_>>();
+        conflicts.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(conflicts
+            .into_iter()
+            .map(|(version, mut tags)| {
+                tags.sort();
+                let tags = tags
+                    .iter()
+                    .map(|(name, commit_id)| {
+                        format!(r#""{}" ({})"#, name, &commit_id.to_string()[..7])
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("Version {version} is tagged on different commits: {tags}")
+            })
+            .collect())
+    }
+
+    // Tags whose version does not parse, e.g. "v1.2.3.4" from legacy tooling, are no version
+    // sources. Names without a leading digit, e.g. "latest", are no versions to begin with.
+    fn skipped_version_tags(&self) -> Result<Vec<String>> {
+        let mut skipped = Vec::new();
+        let tag_names = self.repo.tag_names(None)?;
+        for tag_name in tag_names.iter().flatten() {
+            if let Some(tag_name) = tag_name
+                && self.config.ignored_tag.as_deref() != Some(tag_name)
+                && let Some(captures) = self.config.version_pattern.captures(tag_name)
+                && let Some(version_str) = captures.name(VERSION_ID)
+                && version_str
+                    .as_str()
+                    .starts_with(|c: char| c.is_ascii_digit())
+                && self.version_matching_in(tag_name, &|_| true).is_none()
+            {
+                let version_str = version_str.as_str();
+                let hint = match Version::parse(&Self::loose(version_str)) {
+                    Ok(version) if !self.config.loose_tag_versions => {
+                        format!(", --loose-tag-versions reads it as {version}")
+                    }
+                    _ => String::new(),
+                };
+                skipped.push(format!(
+                    r#"Tag "{tag_name}" is skipped, "{version_str}" is no semantic version{hint}"#
+                ));
+            }
+        }
+        skipped.sort();
+        Ok(skipped)
+    }
+
+    fn release_tag_at(&self, commit_id: Oid) -> Result<Option<VersionSource>> {
+        Ok(self
+            .version_tags_matching(&IS_STABLE_VERSION)?
+            .into_iter()
+            .filter(|source| source.commit_id == commit_id)
+            .max_by(|a, b| a.version.cmp(&b.version)))
     }
 
     fn version_matching_in<T: AsRef<str>, F>(&self, name: T, condition: &F) -> Option<Version>
     where
         F: Fn(&Version) -> bool,
     {
-        if let Some(captures) = self.version_pattern.captures(name.as_ref())
+        if let Some(captures) = self.config.version_pattern.captures(name.as_ref())
             && let Some(version_str) = captures.name(VERSION_ID)
-            && let Ok(version) = Version::parse(version_str.as_str())
+            && let Ok(version) = Version::parse(&match (
+                &self.config.scheme,
+                self.config.loose_tag_versions,
+            ) {
+                (Scheme::CalVer(_), _) => Self::without_leading_zeros(version_str.as_str()),
+                (_, true) => Self::loose(version_str.as_str()),
+                (_, false) => version_str.as_str().to_string(),
+            })
             && condition(&version)
         {
             return Some(version);
@@ -246,7 +1545,7 @@ impl GitVersioner {
     }
 
     fn previous_pre_releases_for(&self, version: &Version) -> Result<Vec<String>> {
-        if !version.pre.is_empty() {
+        if !version.pre.is_empty() || self.config.ignore_prerelease_tags {
             return Ok(Vec::new());
         }
 
@@ -277,6 +1576,22 @@ impl GitVersioner {
             .collect())
     }
 
+    fn without_leading_zeros(calendar_version_string: &str) -> String {
+        let meta_start = calendar_version_string
+            .find(['-', '+'])
+            .unwrap_or(calendar_version_string.len());
+        let (base, rest) = calendar_version_string.split_at(meta_start);
+
+        let components = base
+            .split('.')
+            .map(|component| match component.trim_start_matches('0') {
+                "" if !component.is_empty() => "0",
+                trimmed => trimmed,
+            })
+            .collect::<Vec<_>>();
+        format!("{}{rest}", components.join("."))
+    }
+
     fn loose<S: AsRef<str> + ToString>(semantic_version_string: S) -> String {
         let version_string = semantic_version_string.as_ref();
         let meta_start = version_string
@@ -288,19 +1603,93 @@ impl GitVersioner {
         let components: Vec<&str> = base.split('.').collect();
 
         match components.len() {
+            1 => format!("{base}.0.0{rest}"),
             2 => format!("{base}.0{rest}"),
+            4 => format!("{}{rest}", components[..3].join(".")),
             _ => semantic_version_string.to_string(),
         }
     }
 
+    fn verify_signed_tag_for(&self, source: &VersionSource) -> Result<()> {
+        const SIGNATURE_MARKERS: [&[u8]; 2] = [
+            b"-----BEGIN PGP SIGNATURE-----",
+            b"-----BEGIN SSH SIGNATURE-----",
+        ];
+
+        if !source.is_tag || !IS_RELEASE_VERSION(&source) {
+            return Ok(());
+        }
+
+        let mut unsigned_tags = Vec::new();
+        for tag_name in self.tag_names_for(source)? {
+            let tag_obj = self
+                .repo
+                .revparse_single(&format!("refs/tags/{tag_name}"))?;
+            let is_signed = tag_obj.as_tag().is_some_and(|tag| {
+                let message = tag.message_bytes().unwrap_or_default();
+                SIGNATURE_MARKERS
+                    .iter()
+                    .any(|marker| message.windows(marker.len()).any(|part| part == *marker))
+            });
+            if is_signed {
+                return Ok(());
+            }
+            unsigned_tags.push(tag_name);
+        }
+
+        Err(anyhow!(
+            "Version source tag(s) {} must be signed annotated tags",
+            unsigned_tags.join(", ")
+        ))
+    }
+
+    fn tag_names_for(&self, source: &VersionSource) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        if !source.is_tag {
+            return Ok(names);
+        }
+        let tag_names = self.repo.tag_names(None)?;
+        for tag_name in tag_names.iter().flatten() {
+            if let Some(tag_name) = tag_name
+                && self
+                    .version_matching_in(tag_name, &|version| version == &source.version)
+                    .is_some()
+                && self.tag_id_for(tag_name) == Some(source.commit_id)
+            {
+                names.push(tag_name.to_string());
+            }
+        }
+        Ok(names)
+    }
+
+    // Value of a "channel: <name>" line in the message of an annotated tag
+    fn tag_message_channel_for(&self, name: &str) -> Option<String> {
+        let tag = self
+            .repo
+            .revparse_single(&format!("refs/tags/{name}"))
+            .ok()?;
+        let message = tag.as_tag()?.message().ok().flatten()?;
+        message.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let value = value.trim();
+            (key.trim().eq_ignore_ascii_case("channel") && !value.is_empty())
+                .then(|| value.to_string())
+        })
+    }
+
     fn tag_id_for(&self, name: &str) -> Option<Oid> {
-        match self.repo.revparse_single(&format!("refs/tags/{name}")) {
-            Ok(tag_obj) => match tag_obj.as_tag() {
-                Some(tag) => Some(tag.target_id()),
-                None => Some(tag_obj.id()),
-            },
-            Err(_) => None,
+        let mut tag_obj = self
+            .repo
+            .revparse_single(&format!("refs/tags/{name}"))
+            .ok()?;
+        while let Some(tag) = tag_obj.as_tag() {
+            match tag.target_type() {
+                Some(git2::ObjectType::Tag) => tag_obj = tag.target().ok()?, // Nested tag
+                // The target may be missing, which is reported for version sources later on
+                _ => return Some(tag.target_id()),
+            }
         }
+        Some(tag_obj.id())
     }
 
     fn calculate_cal_ver_minor_for(
@@ -434,12 +1823,8 @@ impl GitVersioner {
         major: u64,
         minor: u64,
     ) -> Result<bool> {
-        let mut revision_walk = self.repo.revwalk()?;
-        revision_walk.push(head_id)?;
-        revision_walk.set_sorting(git2::Sort::TOPOLOGICAL)?;
-
-        for oid in revision_walk.skip(1) {
-            let commit = self.repo.find_commit(oid?)?;
+        for oid in self.revision_walk(head_id, false)?.skip(1) {
+            let commit = self.find_commit(oid?)?;
             if Self::commit_year_for(&commit) != year {
                 continue;
             }
@@ -466,6 +1851,21 @@ impl GitVersioner {
         (time::UNIX_EPOCH + seconds_since_epoch).into()
     }
 
+    fn commit_date_time_from_source(&self, commit: &git2::Commit) -> DateTime<FixedOffset> {
+        let time = match self.config.commit_date_source {
+            CommitDateSource::Author => commit.author().when(),
+            _ => commit.time(),
+        };
+        let utc = DateTime::from_timestamp(time.seconds(), 0).unwrap_or_default();
+
+        match self.config.commit_date_source {
+            CommitDateSource::CommitterUtc => utc.fixed_offset(),
+            _ => FixedOffset::east_opt(time.offset_minutes() * 60)
+                .map(|offset| utc.with_timezone(&offset))
+                .unwrap_or_else(|| utc.fixed_offset()),
+        }
+    }
+
     fn version_branches(&self) -> Result<HashSet<VersionSource>> {
         let mut version_branches = HashSet::new();
 
@@ -475,10 +1875,12 @@ impl GitVersioner {
             if let Some(name) = branch.name()?
                 && let BranchType::Release(version) = self.determine_branch_type_by_name(name)
             {
-                let commit = branch.get().peel_to_commit()?;
+                let Some(commit_id) = branch.get().target() else {
+                    continue;
+                };
                 version_branches.insert(VersionSource {
                     version,
-                    commit_id: commit.id(),
+                    commit_id,
                     is_tag: false,
                 });
             }
@@ -494,14 +1896,19 @@ impl GitVersioner {
             let branches = self.repo.branches(Some(git2::BranchType::Remote))?;
             for branch in branches {
                 let (branch, _) = branch?;
+                if is_symbolic(&branch) {
+                    continue;
+                }
                 if let Some(name) = branch.name()? {
                     let name = name.replace(&format!("{remote}/"), "");
                     if let BranchType::Release(version) = self.determine_branch_type_by_name(&name)
                     {
-                        let commit = branch.get().peel_to_commit()?;
+                        let Some(commit_id) = branch.get().target() else {
+                            continue;
+                        };
                         version_branches.insert(VersionSource {
                             version,
-                            commit_id: commit.id(),
+                            commit_id,
                             is_tag: false,
                         });
                     }
@@ -512,72 +1919,90 @@ impl GitVersioner {
         Ok(version_branches)
     }
 
-    fn calculate_version_for_trunk(&self) -> Result<(Version, VersionSource, VersionSource, u64)> {
+    fn calculate_version_for_trunk(
+        &self,
+    ) -> Result<(Version, VersionSource, VersionSource, PreReleaseWeight)> {
+        if let Scheme::CalVer(format) = &self.config.scheme {
+            return self.calculate_cal_ver_for_trunk(format);
+        }
+
         let source = self.find_trunk_version_source()?.unwrap_or(no_source());
         let major_minor_patch_source = source.clone();
-        let head_id = self.repo.head()?.peel_to_commit()?.id();
+        let head_id = self.head()?.peel_to_commit()?.id();
 
         let merge_base_oid = self.merge_base(head_id, source.commit_id)?;
         if head_id == merge_base_oid {
-            return Ok(Self::version_from(&source, PRERELEASE_WEIGHT_MAIN));
+            return Ok(Self::version_from(&source, PreReleaseWeight::Main));
         }
 
         let mut version = source.version.clone();
 
-        if !self.is_commit_message_incrementing {
-            version.minor += 1;
-            version.patch = 0;
-        } else {
-            match self.determine_bump_between(head_id, merge_base_oid)? {
-                CommitBump::Major => {
-                    if version.major == 0 {
-                        version.minor += 1;
-                        version.patch = 0;
-                    } else {
-                        version.major += 1;
-                        version.minor = 0;
-                        version.patch = 0;
-                    }
-                }
-                CommitBump::Minor => {
+        let increment_override = self
+            .branch_override
+            .as_ref()
+            .and_then(|branch_override| branch_override.increment);
+        let bump = match increment_override.or_else(|| self.pull_request_bump()) {
+            Some(bump) => bump,
+            None if self.config.commit_message_incrementing
+                == CommitMessageIncrementing::Disabled =>
+            {
+                CommitBump::Minor
+            }
+            None => self.determine_bump_between(head_id, merge_base_oid)?,
+        };
+        match bump {
+            CommitBump::Major => {
+                if version.major == 0 {
                     version.minor += 1;
                     version.patch = 0;
+                } else {
+                    version.major += 1;
+                    version.minor = 0;
+                    version.patch = 0;
                 }
-                CommitBump::Patch => {
-                    if version.major == 0 && version.minor == 0 {
-                        version.minor += 1;
-                        version.patch = 0;
-                    } else {
-                        version.patch += 1;
-                    }
+            }
+            CommitBump::Minor => {
+                version.minor += 1;
+                version.patch = 0;
+            }
+            CommitBump::Patch => {
+                if version.major == 0 && version.minor == 0 {
+                    version.minor += 1;
+                    version.patch = 0;
+                } else {
+                    version.patch += 1;
                 }
             }
         }
 
-        let (pre_release_number, source) = match self.continuous_delivery {
-            true => {
-                let highest_pre_release = self.find_latest_matching_pre_release(&version)?;
-                let reference_pre_release = highest_pre_release.unwrap_or((0, source));
-                (reference_pre_release.0 + 1, reference_pre_release.1)
-            }
+        // Without version source, the first commit may count as pre-release 0
+        let first_commit_offset =
+            i64::from(source.commit_id.is_zero() && !self.config.increment_on_first_commit);
+        let (pre_release_number, source) = match self.config.continuous_delivery {
+            true => match self.find_latest_matching_pre_release(&version, true)? {
+                Some((number, source)) => (number + 1, source),
+                None => (1 - first_commit_offset, source),
+            },
             false => {
-                let commit_count = self.count_commits_between(head_id, merge_base_oid)?;
-                (commit_count, source)
+                let commit_count =
+                    self.count_pre_release_commits_between(head_id, merge_base_oid)?;
+                (commit_count - first_commit_offset, source)
             }
         };
 
-        version.pre = self.pre_release(&version, pre_release_number)?;
+        version.pre = self.pre_release(&version, pre_release_number, true)?;
         Ok((
             version,
             source,
             major_minor_patch_source,
-            PRERELEASE_WEIGHT_MAIN,
+            PreReleaseWeight::Main,
         ))
     }
 
     fn find_latest_matching_pre_release(
         &self,
         version: &Version,
+        on_trunk: bool,
     ) -> Result<Option<(i64, VersionSource)>> {
         let is_matching_pre_release = |pre: &Version| {
             !pre.pre.is_empty()
@@ -587,50 +2012,234 @@ impl GitVersioner {
         };
 
         let pre_release_versions = self.version_tags_matching(&is_matching_pre_release)?;
+        let head_id = self.head()?.peel_to_commit()?.id();
 
         let highest_prerelease = pre_release_versions
             .into_iter()
+            .filter(|source| self.is_reachable_from(head_id, source.commit_id))
             .filter_map(|source| {
-                self.extract_pre_release_number(&source.version)
+                self.extract_pre_release_number(&source.version, on_trunk)
                     .map(|number| (number, source))
             })
             .max_by_key(|(number, _)| *number);
         Ok(highest_prerelease)
     }
 
-    fn extract_pre_release_number(&self, version: &Version) -> Option<i64> {
+    fn is_reachable_from(&self, head_id: Oid, commit_id: Oid) -> bool {
+        if !self.replacements.is_empty() {
+            return self
+                .ancestors_with_replacements(head_id)
+                .is_ok_and(|ancestors| ancestors.contains(&commit_id));
+        }
+        head_id == commit_id
+            || self
+                .repo
+                .graph_descendant_of(head_id, commit_id)
+                .unwrap_or(false)
+    }
+
+    // Commits reachable from (and including) `from`, taking parents from replacement commits
+    fn ancestors_with_replacements(&self, from: Oid) -> Result<HashSet<Oid>> {
+        let mut ancestors = HashSet::new();
+        let mut pending = vec![from];
+        while let Some(oid) = pending.pop() {
+            if ancestors.insert(oid) {
+                pending.extend(self.find_commit(oid)?.parent_ids());
+            }
+        }
+        Ok(ancestors)
+    }
+
+    // The replacement of a commit if replace refs are applied, otherwise the commit itself
+    fn find_commit(&self, oid: Oid) -> Result<git2::Commit<'_>, git2::Error> {
+        self.repo
+            .find_commit(*self.replacements.get(&oid).unwrap_or(&oid))
+    }
+
+    // Commits reachable from `from` in topological order, children before their parents
+    fn revision_walk(
+        &self,
+        from: Oid,
+        first_parent: bool,
+    ) -> Result<Box<dyn Iterator<Item = Result<Oid>> + '_>> {
+        if self.replacements.is_empty() {
+            let mut revision_walk = self.repo.revwalk()?;
+            revision_walk.push(from)?;
+            revision_walk.set_sorting(git2::Sort::TOPOLOGICAL)?;
+            if first_parent {
+                revision_walk.simplify_first_parent()?;
+            }
+            return Ok(Box::new(revision_walk.map(|oid| Ok(oid?))));
+        }
+
+        // libgit2 walks the original parents, so the replaced graph is sorted here
+        let mut parents: HashMap<Oid, Vec<Oid>> = HashMap::new();
+        let mut children: HashMap<Oid, usize> = HashMap::new();
+        let mut pending = vec![from];
+        while let Some(oid) = pending.pop() {
+            if parents.contains_key(&oid) {
+                continue;
+            }
+            let commit = self.find_commit(oid)?;
+            let ids = match first_parent {
+                true => commit.parent_ids().take(1).collect::<Vec<_>>(),
+                false => commit.parent_ids().collect(),
+            };
+            for parent in &ids {
+                *children.entry(*parent).or_default() += 1;
+            }
+            pending.extend(ids.iter().copied());
+            parents.insert(oid, ids);
+        }
+
+        let mut sorted = Vec::with_capacity(parents.len());
+        let mut ready = vec![from];
+        while let Some(oid) = ready.pop() {
+            sorted.push(oid);
+            for parent in parents[&oid].iter().rev() {
+                let remaining = children.get_mut(parent).unwrap();
+                *remaining -= 1;
+                if *remaining == 0 {
+                    ready.push(*parent);
+                }
+            }
+        }
+        Ok(Box::new(sorted.into_iter().map(Ok)))
+    }
+
+    fn extract_pre_release_number(&self, version: &Version, on_trunk: bool) -> Option<i64> {
         let pre = version.pre.as_str();
+        let prefix = self.pre_release_prefix(version, on_trunk).ok()?;
+
+        if let Some(integer_part) = pre.strip_prefix(&format!("{prefix}.")) {
+            return integer_part.parse::<i64>().ok();
+        }
+        // Padded numbers are joined to the prefix, e.g. "pre0009"
+        let padded_part = pre.strip_prefix(&prefix)?;
+        match self.config.pre_release_number_padding > 0
+            && !padded_part.is_empty()
+            && padded_part.chars().all(|c| c.is_ascii_digit())
+        {
+            true => padded_part.parse::<i64>().ok(),
+            false => None,
+        }
+    }
+
+    fn pre_release(&self, version: &Version, count: i64, on_trunk: bool) -> Result<Prerelease> {
+        let prefix = self.pre_release_prefix(version, on_trunk)?;
+        let width = self.config.pre_release_number_padding;
+        Ok(Prerelease::new(&match width {
+            0 => format!("{}.{}", prefix, count),
+            // Numeric identifiers must not have leading zeros, so the number joins the prefix
+            _ => format!("{prefix}{count:0width$}"),
+        })?)
+    }
+
+    fn branch_override_tag(&self) -> Option<&str> {
+        self.branch_override.as_ref()?.tag.as_deref()
+    }
 
-        let pre_release_tag = if version.patch > 0 && !self.patch_prerelease_tag.is_empty() {
-            &self.patch_prerelease_tag
+    fn pre_release_prefix(&self, version: &Version, on_trunk: bool) -> Result<String> {
+        let pre_release_tag = if let Some(tag) = self.branch_override_tag() {
+            tag
+        } else if on_trunk && !self.config.trunk_prerelease_tag.is_empty() {
+            self.config.trunk_prerelease_tag.as_str()
+        } else if version.patch > 0 && !self.config.patch_prerelease_tag.is_empty() {
+            self.config.patch_prerelease_tag.as_str()
         } else {
-            &self.prerelease_tag
+            self.config.prerelease_tag.as_str()
         };
 
-        let expected_prefix = format!("{}.", pre_release_tag);
-        if !pre.starts_with(&expected_prefix) {
-            return None;
+        Ok(match self.config.pre_release_format {
+            PreReleaseFormat::Counted => pre_release_tag.to_string(),
+            PreReleaseFormat::Dated => format!("{}.{}", pre_release_tag, self.head_day()?),
+        })
+    }
+
+    fn head_day(&self) -> Result<String> {
+        let head_commit = self.head()?.peel_to_commit()?;
+        Ok(Self::commit_date_time_for(&head_commit)
+            .format("%Y%m%d")
+            .to_string())
+    }
+
+    fn count_pre_release_commits_between(&self, from: Oid, to: Oid) -> Result<i64> {
+        if self.config.pre_release_format == PreReleaseFormat::Counted {
+            return self.count_commits_between(from, to);
+        }
+
+        let head_day = self.head_day()?;
+        let mut count = 0;
+        for oid in self.revision_walk(from, false)? {
+            let oid = oid?;
+            if oid == to {
+                break; // Stop counting when the specific commit is reached
+            }
+            let commit = self.find_commit(oid)?;
+            if Self::commit_date_time_for(&commit)
+                .format("%Y%m%d")
+                .to_string()
+                == head_day
+            {
+                count += 1;
+            }
         }
 
-        let integer_part = &pre[expected_prefix.len()..];
-        integer_part.parse::<i64>().ok()
+        Ok(count)
     }
 
-    fn pre_release(&self, version: &Version, count: i64) -> Result<Prerelease> {
-        let pre_release_tag = if version.patch > 0 && !self.patch_prerelease_tag.is_empty() {
-            &self.patch_prerelease_tag
-        } else {
-            &self.prerelease_tag
+    fn calculate_cal_ver_for_trunk(
+        &self,
+        format: &CalVerFormat,
+    ) -> Result<(Version, VersionSource, VersionSource, PreReleaseWeight)> {
+        let source = self.find_trunk_version_source()?.unwrap_or(no_source());
+        let major_minor_patch_source = source.clone();
+        let head_commit = self.head()?.peel_to_commit()?;
+        let head_id = head_commit.id();
+
+        let merge_base_oid = self.merge_base(head_id, source.commit_id)?;
+        if head_id == merge_base_oid {
+            return Ok(Self::version_from(&source, PreReleaseWeight::Main));
+        }
+
+        let (major, minor) =
+            format.major_minor_for(&self.commit_date_time_from_source(&head_commit));
+        let releases_in_month = self
+            .version_tags_matching(&|version: &Version| {
+                IS_STABLE_VERSION(version) && version.major == major && version.minor == minor
+            })?
+            .into_iter()
+            .map(|source| source.version)
+            .collect::<HashSet<_>>();
+        let mut version = Version::new(major, minor, releases_in_month.len() as u64);
+
+        let (pre_release_number, source) = match self.config.continuous_delivery {
+            true => {
+                let highest_pre_release = self.find_latest_matching_pre_release(&version, true)?;
+                let reference_pre_release = highest_pre_release.unwrap_or((0, source));
+                (reference_pre_release.0 + 1, reference_pre_release.1)
+            }
+            false => {
+                let commit_count =
+                    self.count_pre_release_commits_between(head_id, merge_base_oid)?;
+                (commit_count, source)
+            }
         };
 
-        Ok(Prerelease::new(&format!("{}.{}", pre_release_tag, count))?)
+        version.pre = self.pre_release(&version, pre_release_number, true)?;
+        Ok((
+            version,
+            source,
+            major_minor_patch_source,
+            PreReleaseWeight::Main,
+        ))
     }
 
     fn calculate_version_for_release(
         &self,
         release_version: &Version,
-    ) -> Result<(Version, VersionSource, VersionSource, u64)> {
-        let head_id = self.repo.head()?.peel_to_commit()?.id();
+    ) -> Result<(Version, VersionSource, VersionSource, PreReleaseWeight)> {
+        let head_id = self.head()?.peel_to_commit()?.id();
         let current_version = major_minor_comparator(release_version.major, release_version.minor);
 
         let previous_version = if release_version.minor > 0 {
@@ -642,61 +2251,63 @@ impl GitVersioner {
         if let Some(source) = self.find_latest_version_source(false, &current_version)? {
             let merge_base_oid = self.merge_base(head_id, source.commit_id)?;
             if head_id == merge_base_oid {
-                return Ok(Self::version_from(&source, PRERELEASE_WEIGHT_RELEASE));
+                return Ok(Self::version_from(&source, PreReleaseWeight::Release));
             }
 
             let mut new_version = source.version.clone();
             new_version.patch += 1;
             let major_minor_patch_source = source.clone();
 
-            let (pre_release_number, source) = match self.continuous_delivery {
+            let (pre_release_number, source) = match self.config.continuous_delivery {
                 true => {
                     let highest_pre_release =
-                        self.find_latest_matching_pre_release(&new_version)?;
+                        self.find_latest_matching_pre_release(&new_version, false)?;
                     let reference_pre_release = highest_pre_release.unwrap_or((0, source));
                     (reference_pre_release.0 + 1, reference_pre_release.1)
                 }
                 false => {
-                    let commit_count = self.count_commits_between(head_id, merge_base_oid)?;
+                    let commit_count =
+                        self.count_pre_release_commits_between(head_id, merge_base_oid)?;
                     (commit_count, source)
                 }
             };
-            new_version.pre = self.pre_release(&new_version, pre_release_number)?;
+            new_version.pre = self.pre_release(&new_version, pre_release_number, false)?;
 
             Ok((
                 new_version,
                 source,
                 major_minor_patch_source,
-                PRERELEASE_WEIGHT_RELEASE,
+                PreReleaseWeight::Release,
             ))
         } else if let Some(source) = self.find_latest_version_source(true, &previous_version)? {
             let merge_base_oid = self.merge_base(head_id, source.commit_id)?;
             if head_id == merge_base_oid {
-                return Ok(Self::version_from(&source, PRERELEASE_WEIGHT_RELEASE));
+                return Ok(Self::version_from(&source, PreReleaseWeight::Release));
             }
             let major_minor_patch_source = source.clone();
 
-            let (pre_release_number, source) = match self.continuous_delivery {
+            let (pre_release_number, source) = match self.config.continuous_delivery {
                 true => {
                     let highest_pre_release =
-                        self.find_latest_matching_pre_release(release_version)?;
+                        self.find_latest_matching_pre_release(release_version, false)?;
                     let reference_pre_release = highest_pre_release.unwrap_or((0, source));
                     (reference_pre_release.0 + 1, reference_pre_release.1)
                 }
                 false => {
-                    let commit_count = self.count_commits_between(head_id, merge_base_oid)?;
+                    let commit_count =
+                        self.count_pre_release_commits_between(head_id, merge_base_oid)?;
                     (commit_count, source)
                 }
             };
 
             let mut new_version = release_version.clone();
             new_version.patch += 0;
-            new_version.pre = self.pre_release(&new_version, pre_release_number)?;
+            new_version.pre = self.pre_release(&new_version, pre_release_number, false)?;
             Ok((
                 new_version,
                 source,
                 major_minor_patch_source,
-                PRERELEASE_WEIGHT_RELEASE,
+                PreReleaseWeight::Release,
             ))
         } else {
             let version = release_version.clone();
@@ -707,54 +2318,85 @@ impl GitVersioner {
             };
             let major_minor_patch_source = source.clone();
 
-            let (pre_release_number, source) = match self.continuous_delivery {
+            let (pre_release_number, source) = match self.config.continuous_delivery {
                 true => {
                     let highest_pre_release =
-                        self.find_latest_matching_pre_release(&source.version)?;
+                        self.find_latest_matching_pre_release(&source.version, false)?;
                     let reference_pre_release = highest_pre_release.unwrap_or((0, source));
                     (reference_pre_release.0 + 1, reference_pre_release.1)
                 }
                 false => {
                     let mut found_branches = self.find_all_source_branches(head_id)?;
-                    found_branches.sort_by(|a, b| a.branch_type.cmp(&b.branch_type));
-                    let closest_branch = found_branches.first().unwrap();
-                    let commit_count = closest_branch.distance;
+                    found_branches.retain(|found| match &found.branch_type {
+                        BranchType::Release(version) => version != release_version,
+                        _ => true,
+                    });
+                    found_branches.sort_by(|a, b| {
+                        a.branch_type
+                            .cmp(&b.branch_type)
+                            .then_with(|| a.name.cmp(&b.name))
+                    });
+                    let commit_count = match found_branches.first() {
+                        Some(closest_branch) => closest_branch.distance,
+                        None => self.count_pre_release_commits_between(head_id, Oid::ZERO_SHA1)?,
+                    };
                     (commit_count, source)
                 }
             };
 
             let mut version = source.version.clone();
-            version.pre = self.pre_release(&version, pre_release_number)?;
+            version.pre = self.pre_release(&version, pre_release_number, false)?;
             Ok((
                 version,
                 source,
                 major_minor_patch_source,
-                PRERELEASE_WEIGHT_RELEASE,
+                PreReleaseWeight::Release,
             ))
         }
     }
 
     fn merge_base(&self, head_id: Oid, source_id: Oid) -> Result<Oid> {
-        Ok(if source_id.is_zero() {
-            source_id
-        } else {
-            self.repo.merge_base(head_id, source_id)?
-        })
+        if source_id.is_zero() {
+            return Ok(source_id);
+        }
+        if !self.replacements.is_empty() {
+            // The first common commit in topological order has no common descendant
+            let source_ancestors = self.ancestors_with_replacements(source_id)?;
+            for oid in self.revision_walk(head_id, false)? {
+                let oid = oid?;
+                if source_ancestors.contains(&oid) {
+                    return Ok(oid);
+                }
+            }
+            return Ok(Oid::ZERO_SHA1);
+        }
+        match self.repo.merge_base(head_id, source_id) {
+            Ok(merge_base) => Ok(merge_base),
+            // Unrelated histories, e.g. an orphan branch, count all commits reachable from HEAD
+            Err(error) if error.code() == git2::ErrorCode::NotFound => Ok(Oid::ZERO_SHA1),
+            Err(error) => Err(error.into()),
+        }
     }
 
     fn calculate_version_for_feature(
         &self,
         name: &str,
-    ) -> Result<(Version, VersionSource, VersionSource, u64)> {
-        let head_id = self.repo.head()?.peel_to_commit()?.id();
+    ) -> Result<(Version, VersionSource, VersionSource, PreReleaseWeight)> {
+        let head_id = self.head()?.peel_to_commit()?.id();
         let mut found_branches = self.find_all_source_branches(head_id)?;
 
-        found_branches.sort_by(|a, b| {
-            a.distance
-                .cmp(&b.distance)
-                .then_with(|| a.branch_type.cmp(&b.branch_type))
-        });
+        found_branches.sort_by(FoundBranch::closeness);
         let closest_branch = found_branches.first();
+        if self.config.verbose
+            && let [closest, next, ..] = found_branches.as_slice()
+            && closest.distance == next.distance
+            && closest.branch_type != next.branch_type
+        {
+            eprintln!(
+                "Branches {} and {} are both {} commits away from HEAD, basing version on {}",
+                closest.name, next.name, closest.distance, closest.name
+            );
+        }
         let fallback = (
             Version::new(0, 1, 0),
             VersionSource {
@@ -767,7 +2409,7 @@ impl GitVersioner {
                 commit_id: Oid::ZERO_SHA1,
                 is_tag: false,
             },
-            0,
+            PreReleaseWeight::Feature,
         );
 
         let base = match closest_branch {
@@ -775,7 +2417,9 @@ impl GitVersioner {
             Some(found_branch) => match &found_branch.branch_type {
                 BranchType::Trunk => self.calculate_version_for_trunk(),
                 BranchType::Release(version) => self.calculate_version_for_release(version),
-                BranchType::Other(name) => panic!("Unexpected branch type: {name}"),
+                BranchType::Feature(name) | BranchType::Other(name) => {
+                    panic!("Unexpected branch type: {name}")
+                }
             },
         }
         .unwrap_or(fallback);
@@ -789,14 +2433,40 @@ impl GitVersioner {
             return Ok(base);
         }
 
-        let (mut base_version, source, major_minor_patch_source, _) = base;
+        let (mut base_version, source, major_minor_patch_source, weight) = base;
+        let on_trunk = weight == PreReleaseWeight::Main;
+
+        if self.config.feature_branch_mode == FeatureBranchMode::Metadata
+            && let Some(found_branch) = closest_branch
+        {
+            // The base branch counted the feature commits, so its pre-release is rewound to the
+            // commit the feature branched from
+            if !self.config.continuous_delivery
+                && let Some(number) = self.extract_pre_release_number(&base_version, on_trunk)
+            {
+                let feature_commits =
+                    self.count_pre_release_commits_between(head_id, found_branch.merge_base)?;
+                base_version.pre =
+                    self.pre_release(&base_version, number - feature_commits, on_trunk)?;
+            }
+            base_version.build = BuildMetadata::new(&format!(
+                "{}.{}",
+                self.pre_release_label_for(name)?,
+                distance
+            ))?;
+            return Ok((base_version, source, major_minor_patch_source, weight));
+        }
 
-        base_version.pre = Prerelease::new(&format!("{}.{}", Self::escaped(name), distance))?;
+        base_version.pre = Prerelease::new(&format!(
+            "{}.{}",
+            self.pre_release_label_for(name)?,
+            distance
+        ))?;
         Ok((
             base_version,
             source,
             major_minor_patch_source,
-            PRERELEASE_WEIGHT_FEATURE,
+            PreReleaseWeight::Feature,
         ))
     }
 
@@ -806,6 +2476,9 @@ impl GitVersioner {
         let branches = self.repo.branches(None)?;
         for branch in branches {
             let (branch, branch_type) = branch?;
+            if is_symbolic(&branch) {
+                continue; // Remote HEAD, e.g. origin/HEAD -> origin/main
+            }
             let cleaned_name = match branch_type {
                 git2::BranchType::Local => branch.name()?,
                 git2::BranchType::Remote => {
@@ -818,29 +2491,44 @@ impl GitVersioner {
             };
             if let Some(name) = cleaned_name {
                 let branch_type = self.determine_branch_type_by_name(name);
-                if let BranchType::Other(_) = branch_type {
+                if let BranchType::Feature(_) | BranchType::Other(_) = branch_type {
                     continue;
                 }
 
-                let branch_id = branch.get().peel_to_commit()?.id();
-                let merge_base = self.merge_base(count_reference, branch_id)?;
+                let Ok(branch_commit) = branch.get().peel_to_commit() else {
+                    continue; // Commit object is missing, e.g. after pruning
+                };
+                let branch_id = branch_commit.id();
+                let merge_base = match self.merge_base(count_reference, branch_id) {
+                    Ok(merge_base) if !merge_base.is_zero() => merge_base,
+                    _ => continue, // Unrelated history, e.g. an orphan branch
+                };
                 let distance = self.count_commits_between(count_reference, merge_base)?;
 
                 found_branches.push(FoundBranch {
+                    name: name.to_string(),
                     branch_type,
                     distance,
+                    merge_base,
                 });
             }
         }
         Ok(found_branches)
     }
 
+    fn commits_since_release_branch(&self, head_id: Oid) -> Result<i64> {
+        let distance = self
+            .find_all_source_branches(head_id)?
+            .into_iter()
+            .filter(|found| matches!(found.branch_type, BranchType::Release(_)))
+            .map(|found| found.distance)
+            .min();
+        Ok(distance.unwrap_or(-1)) // No release branch shares history with HEAD
+    }
+
     fn count_commits_between(&self, from: Oid, to: Oid) -> Result<i64> {
-        let mut revision_walk = self.repo.revwalk()?;
-        revision_walk.push(from)?;
-        revision_walk.set_sorting(git2::Sort::TOPOLOGICAL)?;
         let mut count = 0;
-        for oid in revision_walk {
+        for oid in self.revision_walk(from, false)? {
             let oid = oid?;
             if oid == to {
                 break; // Stop counting when the specific commit is reached
@@ -851,29 +2539,103 @@ impl GitVersioner {
         Ok(count)
     }
 
+    fn commit_subjects_between(&self, from: Oid, to: Oid) -> Result<Vec<String>> {
+        let mut subjects = Vec::new();
+        for oid in self.revision_walk(from, false)? {
+            let oid = oid?;
+            if oid == to {
+                break; // Stop collecting when the specific commit is reached
+            }
+            let Ok(commit) = self.find_commit(oid) else {
+                continue;
+            };
+            subjects.push(
+                commit
+                    .summary()
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default()
+                    .to_string(),
+            );
+        }
+
+        Ok(subjects)
+    }
+
+    fn pull_request_bump(&self) -> Option<CommitBump> {
+        if self.config.pr_label_bumps.is_empty() {
+            return None;
+        }
+        let path = env::var_os("GITHUB_EVENT_PATH")?;
+        // Payloads of other events, or unreadable ones, leave the bump to the commits
+        let event: serde_json::Value = serde_json::from_slice(&std::fs::read(path).ok()?).ok()?;
+        let labels = event.get("pull_request")?.get("labels")?.as_array()?;
+        labels
+            .iter()
+            .filter_map(|label| label.get("name")?.as_str())
+            .filter_map(|name| {
+                self.config
+                    .pr_label_bumps
+                    .iter()
+                    .find(|(label, _)| label == name)
+                    .map(|(_, bump)| *bump)
+            })
+            .min() // Major sorts first
+    }
+
     fn determine_bump_between(&self, from: Oid, to: Oid) -> Result<CommitBump> {
-        let mut revision_walk = self.repo.revwalk()?;
-        revision_walk.push(from)?;
-        revision_walk.set_sorting(git2::Sort::TOPOLOGICAL)?;
+        let first_parent = self.config.bump_scan == BumpScan::FirstParent
+            || self.config.commit_message_incrementing
+                == CommitMessageIncrementing::MergeMessageOnly;
         let mut commit_bump = CommitBump::Patch;
-        for oid in revision_walk {
+        let mut unparsed_messages = 0;
+        // First parents only skip commits inside merged branches
+        'walk: for oid in self.revision_walk(from, first_parent)? {
             let oid = oid?;
             if oid == to {
                 break; // Stop counting when the specific commit is reached
             }
-            if let CommitBump::Patch = commit_bump
-                && let Ok(commit) = self.repo.find_commit(oid)
-                && let Ok(message) = commit.message()
-                && let Ok(conventional_commit) = parse(message.trim())
-            {
-                if conventional_commit.is_breaking_change {
-                    return Ok(CommitBump::Major);
-                }
-                if let CommitType::Feature = conventional_commit.commit_type {
-                    commit_bump = CommitBump::Minor;
+            let Ok(commit) = self.find_commit(oid) else {
+                continue;
+            };
+            let mut messages = vec![commit.message().unwrap_or_default().trim()];
+            if first_parent && commit.parent_count() > 1 {
+                // e.g. GitHub records the pull request title below "Merge pull request #1 ..."
+                messages.push(commit.body().ok().flatten().unwrap_or_default().trim());
+            }
+            for message in messages {
+                let CommitBump::Patch = commit_bump else {
+                    continue;
+                };
+                match parse(message) {
+                    Ok(conventional_commit) => {
+                        if conventional_commit.is_breaking_change {
+                            commit_bump = CommitBump::Major;
+                            break 'walk;
+                        }
+                        if let CommitType::Feature = conventional_commit.commit_type {
+                            commit_bump = CommitBump::Minor;
+                        }
+                    }
+                    Err(_) if !message.is_empty() => {
+                        unparsed_messages += 1;
+                        if self.config.verbose {
+                            eprintln!(
+                                "Commit {} is no conventional commit: {}",
+                                &oid.to_string()[..7],
+                                message.lines().next().unwrap_or_default()
+                            );
+                        }
+                    }
+                    Err(_) => {}
                 }
             }
         }
+        if self.config.verbose && unparsed_messages > 0 {
+            eprintln!(
+                "{unparsed_messages} commit message(s) are no conventional commits and did not affect the increment"
+            );
+        }
 
         Ok(commit_bump)
     }
@@ -891,14 +2653,31 @@ impl GitVersioner {
             self.version_tags_matching(&IS_STABLE_VERSION)?
                 .into_iter()
                 .chain(self.version_branches()?)
-                .chain(self.remote_version_branches()?)
+                .chain(match self.config.include_remote_branches {
+                    true => self.remote_version_branches()?,
+                    false => HashSet::new(),
+                })
                 .collect()
         } else {
             self.version_tags_matching(&IS_STABLE_VERSION)?
         };
 
+        let (available, missing): (Vec<VersionSource>, Vec<VersionSource>) = sources
+            .into_iter()
+            .partition(|source| self.is_available(source));
+        if available.is_empty() && !missing.is_empty() {
+            return Err(anyhow!(
+                "No usable version source, all candidates reference missing objects: {}",
+                missing
+                    .iter()
+                    .map(|source| format!("{} ({})", source.version, source.commit_id))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
         let mut all_sources = HashSet::from([no_source()]);
-        all_sources.extend(sources);
+        all_sources.extend(available);
 
         let mut matching_tags = all_sources
             .iter()
@@ -907,16 +2686,61 @@ impl GitVersioner {
             .cloned()
             .collect::<Vec<_>>();
 
-        matching_tags.sort_by(|a, b| a.version.cmp(&b.version));
+        let head_id = self.head()?.peel_to_commit()?.id();
+        matching_tags.sort_by(|a, b| {
+            a.version
+                .cmp(&b.version)
+                .then_with(|| match self.config.tag_selection {
+                    TagSelection::HighestVersion => std::cmp::Ordering::Equal,
+                    TagSelection::MostRecentCommit => self
+                        .commit_time_of(a.commit_id)
+                        .cmp(&self.commit_time_of(b.commit_id)),
+                })
+                .then_with(|| a.is_tag.cmp(&b.is_tag))
+                .then_with(|| {
+                    self.is_reachable_from(head_id, a.commit_id)
+                        .cmp(&self.is_reachable_from(head_id, b.commit_id))
+                })
+                .then_with(|| a.commit_id.cmp(&b.commit_id))
+        });
         Ok(matching_tags.last().cloned())
     }
 
+    fn commit_time_of(&self, commit_id: Oid) -> Option<i64> {
+        self.repo
+            .find_commit(commit_id)
+            .ok()
+            .map(|commit| commit.time().seconds())
+    }
+
+    fn uncommitted_changes(&self) -> Result<u64> {
+        if self.repo.is_bare() {
+            return Ok(0); // Without a working tree, nothing can be uncommitted
+        }
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(true).recurse_untracked_dirs(true);
+        Ok(self.repo.statuses(Some(&mut options))?.len() as u64)
+    }
+
+    fn is_available(&self, source: &VersionSource) -> bool {
+        if source.commit_id.is_zero() || self.repo.find_commit(source.commit_id).is_ok() {
+            return true;
+        }
+        if self.config.verbose {
+            eprintln!(
+                "Skipping version source {} at {}: commit object is missing",
+                source.version, source.commit_id
+            );
+        }
+        false
+    }
+
     fn version_from(
         source: &VersionSource,
-        fallback_weight: u64,
-    ) -> (Version, VersionSource, VersionSource, u64) {
+        fallback_weight: PreReleaseWeight,
+    ) -> (Version, VersionSource, VersionSource, PreReleaseWeight) {
         let prerelease_weight = if source.is_tag {
-            PRERELEASE_WEIGHT_TAG
+            PreReleaseWeight::Tag
         } else {
             fallback_weight
         };
@@ -929,6 +2753,16 @@ impl GitVersioner {
     }
 }
 
+impl VersionSource {
+    fn kind(&self) -> &'static str {
+        match (self.commit_id.is_zero(), self.is_tag) {
+            (true, _) => "None",
+            (false, true) => "Tag",
+            (false, false) => "Branch",
+        }
+    }
+}
+
 fn no_source() -> VersionSource {
     VersionSource {
         version: Version::parse("0.0.0").unwrap(),
@@ -956,43 +2790,99 @@ fn major_minor_comparator(major: u64, minor: u64) -> Comparator {
 }
 
 impl GitVersion {
-    #[allow(clippy::too_many_arguments)]
-    fn new(
-        version: Version,
-        previous_pre_releases: Vec<String>,
-        branch_name: String,
-        source: Oid,
-        major_minor_patch_source: Oid,
-        prerelease_weight: u64,
-        head: Reference,
-        cal_ver_date: DateTime<Utc>,
-        cal_ver_minor: u64,
-        assembly_informational_format: &str,
-    ) -> Self {
+    // The leading identifiers up to the first numeric one, e.g. "alpha.beta" of "alpha.beta.3"
+    // or "pre" of the dated "pre.20240309.1"
+    fn pre_release_label_of(pre: &Prerelease) -> String {
+        pre.as_str()
+            .split('.')
+            .take_while(|identifier| !identifier.chars().all(|c| c.is_ascii_digit()))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    // Splits a pre-release whose number is joined to the label, e.g. "pre0009" into "pre" and 9
+    fn padded_pre_release_of(pre: &Prerelease) -> Option<(String, u64)> {
+        let pre = pre.as_str();
+        let label = pre.trim_end_matches(|c: char| c.is_ascii_digit());
+        if label.len() == pre.len() || label.is_empty() || label.ends_with('.') {
+            return None; // No joined number
+        }
+        Some((label.to_string(), pre[label.len()..].parse().ok()?))
+    }
+
+    fn new(mut version: Version, context: VersionContext) -> Self {
+        let VersionContext {
+            previous_pre_releases,
+            branch_name,
+            source,
+            major_minor_patch_source,
+            prerelease_weight,
+            weight_base,
+            snapshot,
+            pre_release_number_padding,
+            head,
+            commit_date_time,
+            cal_ver_date,
+            cal_ver_minor,
+            commits_since_version_source,
+            version_source_distance,
+            commits_since_release_branch,
+            uncommitted_changes,
+            source_tag,
+            channel,
+            preferred_tag_prefix,
+            build_metadata_format,
+            build_metadata_mode,
+            rebuild_counter,
+            sem_ver_format,
+            assembly_informational_format,
+        } = context;
+
+        // Pre-releases of tags may end with any identifier, e.g. "rc.final"
         let pre_release_number = version
             .pre
             .as_str()
             .split('.')
-            .nth(1)
-            .unwrap_or("0")
-            .parse()
-            .unwrap();
+            .skip(1)
+            .last()
+            .and_then(|number| number.parse().ok())
+            .or_else(|| match pre_release_number_padding {
+                0 => None,
+                _ => Self::padded_pre_release_of(&version.pre).map(|(_, number)| number),
+            })
+            .unwrap_or(0);
+
+        let weighted_pre_release_number =
+            pre_release_number + weight_base.unwrap_or(prerelease_weight.value());
+
+        if snapshot && !version.pre.is_empty() {
+            // The counters above remain available as PreReleaseNumber and WeightedPreReleaseNumber
+            version.pre = Prerelease::new(SNAPSHOT_PRE_RELEASE).unwrap();
+        }
 
-        let weighted_pre_release_number = pre_release_number + prerelease_weight;
+        let pre_release_label = match pre_release_number_padding {
+            0 => None,
+            _ => Self::padded_pre_release_of(&version.pre).map(|(label, _)| label),
+        }
+        .unwrap_or_else(|| Self::pre_release_label_of(&version.pre));
 
         let commit = head.peel_to_commit().unwrap();
         let sha = commit.id().to_string();
         let short_sha = sha[..7].to_string();
-        let commit_date_time = GitVersioner::commit_date_time_for(&commit);
+        let describe = match (source_tag, commits_since_version_source) {
+            (None, _) => "".to_string(),
+            (Some(tag), 0) => tag.to_string(),
+            (Some(tag), distance) => format!("{tag}-{distance}-g{short_sha}"),
+        };
         let commit_date = commit_date_time.format("%Y-%m-%d").to_string();
         let commit_year = commit_date_time.format("%Y").to_string();
         let commit_month = commit_date_time.format("%m").to_string();
         let commit_day = commit_date_time.format("%d").to_string();
 
-        let version_source_sha = if source.is_zero() {
+        let version_source_sha = if source.commit_id.is_zero() {
             "".to_string()
         } else {
-            source.to_string()
+            source.commit_id.to_string()
         };
         let major_minor_patch_version_source_sha = if major_minor_patch_source.is_zero() {
             "".to_string()
@@ -1005,26 +2895,26 @@ impl GitVersion {
             patch: version.patch,
             previous_pre_releases,
             major_minor_patch: format!("{}.{}.{}", version.major, version.minor, version.patch),
+            next_version_tag: format!(
+                "{}{}.{}.{}",
+                preferred_tag_prefix, version.major, version.minor, version.patch
+            ),
+            tag_name: String::new(),
             pre_release_tag: version.pre.to_string(),
             pre_release_tag_with_dash: if version.pre.is_empty() {
                 "".to_string()
             } else {
                 format!("-{}", version.pre.as_str())
             },
-            pre_release_label: version
-                .pre
-                .as_str()
-                .split('.')
-                .next()
-                .unwrap_or("")
-                .to_string(),
+            pre_release_label: pre_release_label.clone(),
             pre_release_label_with_dash: if version.pre.is_empty() {
                 "".to_string()
             } else {
-                format!("-{}", version.pre.as_str().split('.').next().unwrap_or(""))
+                format!("-{pre_release_label}")
             },
             pre_release_number,
             weighted_pre_release_number,
+            weight_source: prerelease_weight.source().to_string(),
             build_metadata: version.build.to_string(),
             sem_ver: version.to_string(),
             assembly_sem_ver: format!("{}.{}.{}.0", version.major, version.minor, version.patch),
@@ -1038,8 +2928,13 @@ impl GitVersion {
             sha,
             short_sha,
             version_source_sha,
+            version_source_kind: source.kind().to_string(),
             major_minor_patch_version_source_sha,
-            commits_since_version_source: 0,
+            commits_since_version_source,
+            version_source_distance,
+            commits_since_release_branch,
+            describe,
+            channel,
             commit_date,
             commit_year,
             commit_month,
@@ -1049,14 +2944,65 @@ impl GitVersion {
             cal_ver_day: cal_ver_date.format("%d").to_string(),
             cal_ver_minor,
             branch_name,
-            full_build_meta_data: "".to_string(),
-            uncommitted_changes: 0,
+            full_build_meta_data: version.build.to_string(),
+            uncommitted_changes,
+            repository_path: String::new(),
+            git_dir: String::new(),
+        };
+
+        let mut metadata = match build_metadata_format.is_empty() {
+            true => build_metadata_mode
+                .metadata_for(commits_since_version_source, &result.short_sha)
+                .unwrap_or_else(|| version.build.to_string()),
+            false => Self::sanitized_build_metadata(&result.format(build_metadata_format)),
         };
+        if let Some(rebuild_counter) = rebuild_counter {
+            metadata =
+                Self::sanitized_build_metadata(&format!("{metadata}.rebuild.{rebuild_counter}"));
+        }
+        if metadata != version.build.as_str()
+            && !metadata.is_empty()
+            && let Ok(build) = BuildMetadata::new(&metadata)
+        {
+            version.build = build;
+            result.build_metadata = metadata.clone();
+            result.full_build_meta_data = metadata;
+            result.full_sem_ver = version.to_string();
+            result.informational_version = version.to_string();
+        }
+
+        // Tags carry no build metadata, regardless of the SemVerFormat
+        result.tag_name = format!(
+            "{}{}",
+            result.next_version_tag, result.pre_release_tag_with_dash
+        );
+
+        if !sem_ver_format.is_empty() {
+            result.sem_ver = result.format(sem_ver_format);
+            result.full_sem_ver = result.sem_ver.clone();
+        }
 
         result.informational_version = result.format(assembly_informational_format);
         result
     }
 
+    fn sanitized_build_metadata(metadata: &str) -> String {
+        metadata
+            .split('.')
+            .map(|identifier| {
+                identifier
+                    .chars()
+                    .map(|c| match c.is_ascii_alphanumeric() || c == '-' {
+                        true => c,
+                        false => '-',
+                    })
+                    .collect::<String>()
+            })
+            .filter(|identifier| !identifier.is_empty())
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
     fn format(&self, format: &str) -> String {
         let mut values = std::collections::HashMap::new();
         if let Ok(serialized) = serde_json::to_value(self)
@@ -1071,8 +3017,7 @@ impl GitVersion {
             }
         }
 
-        let expression_pattern = Regex::new(r"\{([^{}]+)}").unwrap();
-        expression_pattern
+        EXPRESSION_PATTERN
             .replace_all(format, |captures: &regex::Captures<'_>| {
                 let whole = captures.get(0).map(|m| m.as_str()).unwrap_or_default();
                 let expression = captures.get(1).map(|m| m.as_str()).unwrap_or_default();
@@ -1119,6 +3064,14 @@ impl Display for GitVersion {
     }
 }
 
+fn is_symbolic(branch: &git2::Branch) -> bool {
+    let reference = branch.get();
+    matches!(reference.symbolic_target(), Ok(Some(_)))
+        || reference
+            .shorthand()
+            .is_ok_and(|name| name.ends_with("/HEAD"))
+}
+
 fn get_method_name<R, O, F>(_: F) -> &'static str
 where
     F: for<'a> Fn(&'a R) -> &'a O,
@@ -1127,3 +3080,43 @@ where
     let full_name = std::any::type_name::<F>();
     full_name.rsplit("::").next().unwrap_or(full_name)
 }
+
+#[cfg(any(feature = "ffi", feature = "python"))]
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "Version calculation panicked".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GitVersioner;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::major("2", "2.0.0")]
+    #[case::major_minor("2.1", "2.1.0")]
+    #[case::major_minor_patch("2.1.3", "2.1.3")]
+    #[case::four_components("2.1.3.4", "2.1.3")]
+    #[case::major_with_pre_release("2-rc.1", "2.0.0-rc.1")]
+    #[case::major_minor_with_build_metadata("2.1+build.5", "2.1.0+build.5")]
+    #[case::four_components_with_pre_release("2.1.3.4-rc.1", "2.1.3-rc.1")]
+    #[case::pre_release_with_dots("2.1-rc.1.2", "2.1.0-rc.1.2")]
+    fn test_loose_pads_or_truncates_to_three_components(
+        #[case] version: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(GitVersioner::loose(version), expected);
+    }
+
+    #[rstest]
+    #[case::five_components("1.2.3.4.5")]
+    #[case::five_components_with_pre_release("1.2.3.4.5-rc.1")]
+    fn test_loose_leaves_other_versions_unchanged(#[case] version: &str) {
+        assert_eq!(GitVersioner::loose(version), version);
+    }
+}
@@ -1,7 +1,17 @@
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use git_versioner::GitVersioner;
-use git_versioner::config::{Configuration, load_configuration};
-use git_versioner::exporter::export_to_build_agent;
+use git_versioner::apply::{
+    apply_gradle, apply_helm, apply_kustomize, apply_maven, apply_version_file,
+};
+use git_versioner::config::{ApplyTarget, Command, Configuration, load_configuration};
+use git_versioner::exporter::{
+    Exporter, GitHubExporter, GitLabExporter, check_output_file_format, check_variable_names,
+    export_docker_args, export_docker_labels, export_gitlab_release, export_to_build_agent,
+    export_to_file, variables_of,
+};
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::path::Path;
 
 fn main() -> Result<()> {
     let config = load_configuration()?;
@@ -12,17 +22,181 @@ fn main() -> Result<()> {
     if *config.verbose() {
         print(&config);
     }
+    // Printed before any calculation, so that it is also available if the calculation fails
+    if *config.print_graph() {
+        eprint!("{}", GitVersioner::graph(&config)?);
+    }
+
+    if let Some(Command::Plan { output }) = config.command() {
+        let plan = GitVersioner::release_plan(&config)?;
+        match output.as_str() {
+            "json" => println!("{}", serde_json::to_string_pretty(&plan)?),
+            _ => print!("{plan}"),
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::VerifyTag { tag }) = config.command() {
+        let version = GitVersioner::verify_tag(&config, tag)?;
+        println!(r#"Tag "{tag}" matches version {version}"#);
+        return Ok(());
+    }
+
+    if let Some(Command::Apply { target }) = config.command() {
+        let version = GitVersioner::calculate_version(&config)?;
+        // Relative to the work tree, as --path may name any directory or file within the repository
+        let work_tree = Path::new(&version.repository_path);
+        let updated = match target {
+            ApplyTarget::Maven {
+                pom,
+                include_parent,
+                modules,
+            } => apply_maven(&version, &work_tree.join(pom), *include_parent, *modules)?,
+            ApplyTarget::Gradle { file, key } => {
+                apply_gradle(&version, &work_tree.join(file), key)?
+            }
+            ApplyTarget::Helm {
+                chart,
+                app_version_only,
+            } => apply_helm(&version, &work_tree.join(chart), *app_version_only)?,
+            ApplyTarget::Kustomize { file, image } => {
+                apply_kustomize(&version, &work_tree.join(file), image)?
+            }
+            ApplyTarget::VersionFile {
+                path,
+                variable,
+                check,
+            } => apply_version_file(&version, &work_tree.join(path), variable, *check)?,
+        };
+        for path in updated {
+            println!("Updated {}", path.display());
+        }
+        return Ok(());
+    }
+
+    if *config.increment_preview() {
+        let preview = GitVersioner::increment_preview(&config)?;
+        println!("{}", serde_json::to_string_pretty(&preview)?);
+        return Ok(());
+    }
+
+    if let [ref_a, ref_b] = config.compare() {
+        let comparison = GitVersioner::compare(&config, ref_a, ref_b)?;
+        println!("{}", serde_json::to_string_pretty(&comparison)?);
+        return Ok(());
+    }
+
+    if *config.create_tag() {
+        let version = GitVersioner::create_tag(&config)?;
+        println!(
+            r#"Created tag "{}" for version {}"#,
+            version.tag_name, version.full_sem_ver
+        );
+        return Ok(());
+    }
+
+    if *config.list_version_sources() {
+        let sources = GitVersioner::version_sources(&config)?;
+        println!("{}", serde_json::to_string_pretty(&sources)?);
+        return Ok(());
+    }
+
+    if *config.all_branches() {
+        let versions = GitVersioner::calculate_all_branches(&config)?;
+        match config.output() {
+            "json" => println!("{}", serde_json::to_string_pretty(&versions)?),
+            "jsonl" => {
+                for version in &versions {
+                    println!("{}", serde_json::to_string(version)?);
+                }
+            }
+            output => {
+                return Err(anyhow!(
+                    r#"Invalid value "{}" for Output. Should be "json" or "jsonl"."#,
+                    output
+                ));
+            }
+        }
+        return Ok(());
+    }
+
+    // Checked before calculating, so that nothing is printed for an invalid combination
+    match config.output() {
+        "json" => {
+            if config.output_file().is_some() {
+                check_output_file_format(config.output_file_format())?;
+            }
+        }
+        output @ ("env-github" | "env-gitlab") => {
+            if !config.omit_fields().is_empty() || config.output_file().is_some() {
+                return Err(anyhow!(
+                    r#"Output "{}" prints the variables as exported, which OmitFields and OutputFile do not apply to. Use ExportVariables to select variables or use Output "json"."#,
+                    output
+                ));
+            }
+        }
+        output => {
+            return Err(anyhow!(
+                r#"Invalid value "{}" for Output. Should be "json", "env-github" or "env-gitlab"."#,
+                output
+            ));
+        }
+    }
 
     let version = GitVersioner::calculate_version(&config)?;
 
-    let json = serde_json::to_string_pretty(&version)?;
-    println!("{json}");
+    match (config.output(), config.omit_fields()) {
+        ("json", []) => write_output(&version, &config)?,
+        ("json", omit_fields) => {
+            check_variable_names(&version, omit_fields, "OmitFields")?;
+            // Parsed into an ordered map, so the remaining fields keep the order of the full output
+            let mut output: IndexMap<String, serde_json::Value> =
+                serde_json::from_str(&serde_json::to_string(&version)?)?;
+            output.retain(|key, _| !omit_fields.contains(key));
+            write_output(&output, &config)?;
+        }
+        ("env-github", _) => print!(
+            "{}",
+            GitHubExporter { annotations: false }.render(
+                &version,
+                &variables_of(&version, config.export_variables())?
+            )
+        ),
+        ("env-gitlab", _) => print!(
+            "{}",
+            GitLabExporter.render(
+                &version,
+                &variables_of(&version, config.export_variables())?
+            )
+        ),
+        _ => unreachable!("checked before calculating"),
+    }
 
-    export_to_build_agent(&version)?;
+    if let Some(path) = config.gitlab_release_file() {
+        export_gitlab_release(&version, path)?;
+    }
+    if let Some(path) = config.docker_args_file() {
+        export_docker_args(&version, path)?;
+    }
+    if let Some(path) = config.docker_labels_file() {
+        export_docker_labels(&version, path)?;
+    }
+
+    export_to_build_agent(&version, &config)?;
 
     Ok(())
 }
 
+fn write_output<T: Serialize, C: Configuration>(output: &T, config: &C) -> Result<()> {
+    let json = serde_json::to_string_pretty(output)?;
+    println!("{json}");
+
+    if let Some(path) = config.output_file() {
+        export_to_file(output, path, config.output_file_format())?;
+    }
+    Ok(())
+}
+
 fn print<T: Configuration>(config: &T) {
     println!("Configuration:");
     println!("{}", toml::to_string(&config.print()).unwrap());
@@ -0,0 +1,117 @@
+use crate::config::{ConfigurationFile, DefaultConfig};
+use crate::{GitVersioner, GitVersionerConfig, panic_message};
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use serde_json::Value;
+use std::panic::{self, AssertUnwindSafe};
+
+create_exception!(git_versioner, ConfigurationError, PyException);
+create_exception!(git_versioner, RepositoryError, PyException);
+
+/// Calculates the version of the repository at `path`.
+///
+/// Configuration fields are given as keyword arguments in snake_case,
+/// e.g. `calculate(".", pre_release_tag="alpha")`.
+#[pyfunction]
+#[pyo3(signature = (path, **config))]
+pub fn calculate<'py>(
+    py: Python<'py>,
+    path: &str,
+    config: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let config = DefaultConfig::builder()
+        .path(path)
+        .overrides(configuration_from(config)?)
+        .build();
+    // Invalid configuration values panic while compiling the configuration
+    let config = panic::catch_unwind(AssertUnwindSafe(|| GitVersionerConfig::new(&config)))
+        .map_err(|payload| ConfigurationError::new_err(panic_message(payload)))?
+        .map_err(|error| ConfigurationError::new_err(format!("{error:#}")))?;
+
+    let version = GitVersioner::calculate_version_with(&config)
+        .map_err(|error| RepositoryError::new_err(format!("{error:#}")))?;
+
+    let result = PyDict::new(py);
+    if let Value::Object(fields) = serde_json::to_value(&version)
+        .map_err(|error| RepositoryError::new_err(error.to_string()))?
+    {
+        for (key, value) in &fields {
+            result.set_item(key, to_python(py, value)?)?;
+        }
+    }
+    Ok(result)
+}
+
+#[pymodule]
+fn git_versioner(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(calculate, module)?)?;
+    module.add(
+        "ConfigurationError",
+        module.py().get_type::<ConfigurationError>(),
+    )?;
+    module.add("RepositoryError", module.py().get_type::<RepositoryError>())?;
+    Ok(())
+}
+
+fn configuration_from(config: Option<&Bound<'_, PyDict>>) -> PyResult<ConfigurationFile> {
+    let known_fields = match serde_json::to_value(ConfigurationFile::default()) {
+        Ok(Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
+    };
+
+    let mut fields = serde_json::Map::new();
+    for (key, value) in config.into_iter().flatten() {
+        let key: String = key.extract()?;
+        let name = stringcase::pascal_case(&key);
+        if !known_fields.contains_key(&name) {
+            return Err(ConfigurationError::new_err(format!(
+                "Unknown configuration option {key}"
+            )));
+        }
+        let value = if let Ok(value) = value.extract::<bool>() {
+            Value::Bool(value)
+        } else if let Ok(value) = value.extract::<String>() {
+            Value::String(value)
+        } else if let Ok(values) = value.extract::<Vec<String>>() {
+            Value::Array(values.into_iter().map(Value::String).collect())
+        } else {
+            return Err(ConfigurationError::new_err(format!(
+                "Invalid value for {key}, expected str, bool or list of str"
+            )));
+        };
+        fields.insert(name, value);
+    }
+
+    serde_json::from_value(Value::Object(fields))
+        .map_err(|error| ConfigurationError::new_err(error.to_string()))
+}
+
+fn to_python<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    Ok(match value {
+        Value::Null => py.None().into_bound(py),
+        Value::Bool(value) => value.into_pyobject(py)?.to_owned().into_any(),
+        Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(value), _) => value.into_pyobject(py)?.into_any(),
+            (None, Some(value)) => value.into_pyobject(py)?.into_any(),
+            _ => number.as_f64().into_pyobject(py)?.into_any(),
+        },
+        Value::String(value) => value.into_pyobject(py)?.into_any(),
+        Value::Array(values) => PyList::new(
+            py,
+            values
+                .iter()
+                .map(|value| to_python(py, value))
+                .collect::<PyResult<Vec<_>>>()?,
+        )?
+        .into_any(),
+        Value::Object(fields) => {
+            let result = PyDict::new(py);
+            for (key, value) in fields {
+                result.set_item(key, to_python(py, value)?)?;
+            }
+            result.into_any()
+        }
+    })
+}
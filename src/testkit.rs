@@ -0,0 +1,201 @@
+use crate::config::DefaultConfig;
+use crate::{GitVersion, GitVersioner};
+use chrono::{DateTime, FixedOffset};
+use git2::{BranchType, Commit, Repository, RepositoryInitOptions, Tree};
+use std::path::Path;
+
+pub const MAIN_BRANCH: &str = "trunk";
+
+/// A temporary git repository to calculate versions in, built with libgit2 only.
+pub struct TestRepo {
+    pub config: DefaultConfig,
+    repo: Repository,
+    _temp_dir: tempfile::TempDir, // Keep the temp_dir to prevent it from being deleted
+}
+
+impl TestRepo {
+    pub fn initialize(main_branch: &str) -> Self {
+        let _temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let repo = checked(
+            Repository::init_opts(
+                _temp_dir.path(),
+                RepositoryInitOptions::new().initial_head(main_branch),
+            ),
+            "initialize repository",
+        );
+        let mut git_config = checked(repo.config(), "open repository configuration");
+        checked(
+            git_config.set_str("user.name", "tester"),
+            "configure user.name",
+        );
+        checked(
+            git_config.set_str("user.email", "tester@tests.com"),
+            "configure user.email",
+        );
+        let config = DefaultConfig::builder().path(_temp_dir.path()).build();
+        Self {
+            config,
+            repo,
+            _temp_dir,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.config.path
+    }
+
+    pub fn commit(&self, message: &str) -> (String, String) {
+        let description = format!("commit {message}");
+        let parents = self.head_commit().into_iter().collect::<Vec<_>>();
+        let tree = match parents.first() {
+            Some(parent) => checked(parent.tree(), &description),
+            None => {
+                let mut index = checked(self.repo.index(), &description);
+                let tree_id = checked(index.write_tree(), &description);
+                checked(self.repo.find_tree(tree_id), &description)
+            }
+        };
+        self.create_commit(
+            message,
+            &tree,
+            &parents.iter().collect::<Vec<_>>(),
+            &description,
+        );
+        self.head_sha_and_date()
+    }
+
+    pub fn branch(&self, name: &str) {
+        let description = format!("branch {name}");
+        let head = self
+            .head_commit()
+            .unwrap_or_else(|| panic!("Failed to {description}, because: HEAD has no commit"));
+        checked(self.repo.branch(name, &head, false), &description);
+        self.checkout(name);
+    }
+
+    pub fn checkout(&self, name: &str) {
+        let description = format!("checkout {name}");
+        match self.repo.find_branch(name, BranchType::Local) {
+            Ok(branch) => {
+                let reference = branch.get().name().expect("Branch name is not valid UTF-8");
+                checked(self.repo.set_head(reference), &description);
+            }
+            Err(_) => {
+                let commit = checked(
+                    self.repo
+                        .revparse_single(name)
+                        .and_then(|object| object.peel_to_commit()),
+                    &description,
+                );
+                checked(self.repo.set_head_detached(commit.id()), &description);
+            }
+        }
+        checked(
+            self.repo
+                .checkout_head(Some(git2::build::CheckoutBuilder::new().force())),
+            &description,
+        );
+    }
+
+    pub fn tag(&self, name: &str) -> (String, String) {
+        let description = format!("create tag {name}");
+        let head = checked(
+            self.repo
+                .head()
+                .and_then(|head| head.peel(git2::ObjectType::Commit)),
+            &description,
+        );
+        checked(self.repo.tag_lightweight(name, &head, false), &description);
+        self.head_sha_and_date()
+    }
+
+    pub fn merge(&self, name: &str) {
+        let description = format!("merge {name}");
+        let head = self
+            .head_commit()
+            .unwrap_or_else(|| panic!("Failed to {description}, because: HEAD has no commit"));
+        let other = checked(
+            self.repo
+                .revparse_single(name)
+                .and_then(|object| object.peel_to_commit()),
+            &description,
+        );
+        let mut index = checked(self.repo.merge_commits(&head, &other, None), &description);
+        if index.has_conflicts() {
+            panic!("Failed to {description}, because: the merge has conflicts");
+        }
+        let tree_id = checked(index.write_tree_to(&self.repo), &description);
+        let tree = checked(self.repo.find_tree(tree_id), &description);
+        self.create_commit(
+            &format!("Merge branch '{name}'"),
+            &tree,
+            &[&head, &other],
+            &description,
+        );
+    }
+
+    pub fn graph(&self) -> String {
+        GitVersioner::graph(&self.config)
+            .unwrap_or_else(|error| panic!("Failed to get commit graph, because: {error:#}"))
+    }
+
+    pub fn assert(&self) -> GitVersion {
+        GitVersioner::calculate_version(&self.config).unwrap_or_else(|error| {
+            panic!(
+                "Failed to calculate version, because: {error:#}\nGit Graph:\n  {}",
+                self.graph()
+            )
+        })
+    }
+
+    pub fn commit_and_assert(&self, expected: &str) -> GitVersion {
+        self.commit(expected);
+        let version = self.assert();
+        assert_eq!(
+            version.full_sem_ver,
+            expected,
+            "Expected full_sem_ver: {expected}, found: {}\nGit Graph:\n  {}",
+            version.full_sem_ver,
+            self.graph()
+        );
+        version
+    }
+
+    fn head_commit(&self) -> Option<Commit<'_>> {
+        self.repo.head().ok()?.peel_to_commit().ok()
+    }
+
+    fn create_commit(&self, message: &str, tree: &Tree, parents: &[&Commit], description: &str) {
+        let signature = checked(self.repo.signature(), description);
+        checked(
+            self.repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                &format!("{message}\n"),
+                tree,
+                parents,
+            ),
+            description,
+        );
+    }
+
+    fn head_sha_and_date(&self) -> (String, String) {
+        let commit = self
+            .head_commit()
+            .expect("Failed to get commit hash, because: HEAD has no commit");
+        let time = commit.committer().when();
+        let commit_date = FixedOffset::east_opt(time.offset_minutes() * 60)
+            .and_then(|offset| {
+                DateTime::from_timestamp(time.seconds(), 0).map(|date| date.with_timezone(&offset))
+            })
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .expect("Failed to get commit date");
+
+        (commit.id().to_string(), commit_date)
+    }
+}
+
+fn checked<T>(result: Result<T, git2::Error>, description: &str) -> T {
+    result.unwrap_or_else(|error| panic!("Failed to {description}, because: {error}"))
+}
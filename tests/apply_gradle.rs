@@ -0,0 +1,69 @@
+mod cli;
+mod common;
+
+use crate::cli::{ConfiguredTestRepo, repo};
+use rstest::rstest;
+use std::fs;
+
+#[rstest]
+#[case::existing_key(
+    "# Project properties\norg.gradle.jvmargs=-Xmx2g\nversion=0.0.0\nversionCode=3\n",
+    &[],
+    "# Project properties\norg.gradle.jvmargs=-Xmx2g\nversion=0.1.0-pre.1\nversionCode=3\n"
+)]
+#[case::spaced_separator(
+    "group = com.example\nversion = 0.0.0 \n",
+    &[],
+    "group = com.example\nversion = 0.1.0-pre.1\n"
+)]
+#[case::missing_key(
+    "# Project properties\ngroup=com.example",
+    &[],
+    "# Project properties\ngroup=com.example\nversion=0.1.0-pre.1\n"
+)]
+#[case::commented_key(
+    "#version=0.0.0\n",
+    &[],
+    "#version=0.0.0\nversion=0.1.0-pre.1\n"
+)]
+#[case::crlf_line_endings(
+    "# Project properties\r\nversion=0.0.0\r\ngroup=com.example\r\n",
+    &[],
+    "# Project properties\r\nversion=0.1.0-pre.1\r\ngroup=com.example\r\n"
+)]
+#[case::crlf_insertion(
+    "group=com.example\r\n",
+    &[],
+    "group=com.example\r\nversion=0.1.0-pre.1\r\n"
+)]
+#[case::custom_key(
+    "version=1\nprojectVersion=0.0.0\n",
+    &["--key", "projectVersion"],
+    "version=1\nprojectVersion=0.1.0-pre.1\n"
+)]
+fn test_apply_gradle_sets_version_property(
+    mut repo: ConfiguredTestRepo,
+    #[case] content: &str,
+    #[case] args: &[&str],
+    #[case] expected: &str,
+) {
+    // Outside the repository, so the version is not affected by uncommitted changes
+    let directory = tempfile::tempdir().unwrap();
+    let file = directory.path().join("gradle.properties");
+    fs::write(&file, content).unwrap();
+
+    let output = repo
+        .cmd
+        .args(["apply", "gradle", "--file"])
+        .arg(&file)
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(fs::read_to_string(&file).unwrap(), expected);
+}
@@ -0,0 +1,87 @@
+mod cli;
+mod common;
+
+use crate::cli::{ConfiguredTestRepo, repo};
+use rstest::rstest;
+use std::fs;
+use std::path::Path;
+
+fn fixture(name: &str) -> String {
+    fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/helm")
+            .join(name)
+            .join("Chart.yaml"),
+    )
+    .unwrap()
+}
+
+#[rstest]
+#[case::chart_and_app_version(
+    "service",
+    &[],
+    &[
+        ("version: 0.0.0 #", "version: 0.1.0-pre.1 #"),
+        (r#"appVersion: "0.0.0""#, r#"appVersion: "0.1.0-pre.1""#),
+    ]
+)]
+#[case::app_version_only(
+    "service",
+    &["--app-version-only"],
+    &[(r#"appVersion: "0.0.0""#, r#"appVersion: "0.1.0-pre.1""#)]
+)]
+#[case::missing_app_version(
+    "library",
+    &[],
+    &[
+        ("version: '0.0.0'\n", "version: '0.1.0-pre.1'\nappVersion: \"0.1.0-pre.1\"\n"),
+    ]
+)]
+fn test_apply_helm_sets_versions_of_chart(
+    mut repo: ConfiguredTestRepo,
+    #[case] chart: &str,
+    #[case] args: &[&str],
+    #[case] replacements: &[(&str, &str)],
+) {
+    // Outside the repository, so the version is not affected by uncommitted changes
+    let directory = tempfile::tempdir().unwrap();
+    fs::write(directory.path().join("Chart.yaml"), fixture(chart)).unwrap();
+
+    let output = repo
+        .cmd
+        .args(["apply", "helm", "--chart"])
+        .arg(directory.path())
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let expected = replacements
+        .iter()
+        .fold(fixture(chart), |chart, (from, to)| chart.replace(from, to));
+    assert_eq!(
+        fs::read_to_string(directory.path().join("Chart.yaml")).unwrap(),
+        expected
+    );
+}
+
+#[rstest]
+fn test_apply_helm_fails_for_chart_without_version(mut repo: ConfiguredTestRepo) {
+    let directory = tempfile::tempdir().unwrap();
+    let chart = directory.path().join("Chart.yaml");
+    fs::write(&chart, "apiVersion: v2\nname: service\n").unwrap();
+
+    let output = repo
+        .cmd
+        .args(["apply", "helm", "--chart"])
+        .arg(&chart)
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("has no version to set"));
+}
@@ -0,0 +1,99 @@
+mod cli;
+mod common;
+
+use crate::cli::{ConfiguredTestRepo, repo};
+use rstest::rstest;
+use std::fs;
+use std::path::Path;
+
+fn fixture(name: &str) -> String {
+    fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/kustomize")
+            .join(name)
+            .join("kustomization.yaml"),
+    )
+    .unwrap()
+}
+
+#[rstest]
+#[case::existing_tag(
+    "images",
+    "ghcr.io/acme/service",
+    r#"newTag: "0.0.0""#,
+    r#"newTag: "0.1.0-pre.1""#
+)]
+#[case::missing_tag(
+    "images",
+    "ghcr.io/acme/migrations",
+    "    newName: registry.acme.com/migrations\n",
+    "    newName: registry.acme.com/migrations\n    newTag: 0.1.0-pre.1\n"
+)]
+#[case::missing_image(
+    "images",
+    "ghcr.io/acme/worker",
+    "    newName: registry.acme.com/migrations\n",
+    "    newName: registry.acme.com/migrations\n  - name: ghcr.io/acme/worker\n    newTag: 0.1.0-pre.1\n"
+)]
+#[case::missing_images(
+    "plain",
+    "ghcr.io/acme/service",
+    "- deployment.yaml\n",
+    "- deployment.yaml\nimages:\n  - name: ghcr.io/acme/service\n    newTag: 0.1.0-pre.1\n"
+)]
+fn test_apply_kustomize_sets_tag_of_image(
+    mut repo: ConfiguredTestRepo,
+    #[case] kustomization: &str,
+    #[case] image: &str,
+    #[case] from: &str,
+    #[case] to: &str,
+) {
+    // Outside the repository, so the version is not affected by uncommitted changes
+    let directory = tempfile::tempdir().unwrap();
+    let file = directory.path().join("kustomization.yaml");
+    fs::write(&file, fixture(kustomization)).unwrap();
+
+    let output = repo
+        .cmd
+        .args(["apply", "kustomize", "--image", image, "--file"])
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        fixture(kustomization).replace(from, to)
+    );
+}
+
+#[rstest]
+fn test_apply_kustomize_fails_for_ambiguous_image(mut repo: ConfiguredTestRepo) {
+    let directory = tempfile::tempdir().unwrap();
+    let file = directory.path().join("kustomization.yaml");
+    fs::write(&file, fixture("duplicate")).unwrap();
+
+    let output = repo
+        .cmd
+        .args([
+            "apply",
+            "kustomize",
+            "--image",
+            "ghcr.io/acme/service",
+            "--file",
+        ])
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains(r#"Image "ghcr.io/acme/service" is listed 2 times"#)
+    );
+    assert_eq!(fs::read_to_string(&file).unwrap(), fixture("duplicate"));
+}
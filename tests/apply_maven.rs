@@ -0,0 +1,202 @@
+mod cli;
+mod common;
+
+use crate::cli::{ConfiguredTestRepo, cmd, repo};
+use git_versioner::GitVersion;
+use rstest::rstest;
+use std::fs;
+use std::path::Path;
+
+const FIXTURE_VERSION: &str = "<version>0.0.0</version>";
+const PARENT_VERSION: &str = "<version>3.3.0</version>";
+
+fn copy_fixture(name: &str, target: &Path) {
+    let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/maven")
+        .join(name);
+    copy_directory(&source, target);
+
+    fn copy_directory(source: &Path, target: &Path) {
+        fs::create_dir_all(target).unwrap();
+        for entry in fs::read_dir(source).unwrap() {
+            let path = entry.unwrap().path();
+            let target = target.join(path.file_name().unwrap());
+            if path.is_dir() {
+                copy_directory(&path, &target);
+            } else {
+                fs::copy(&path, &target).unwrap();
+            }
+        }
+    }
+}
+
+fn fixture(path: &str) -> String {
+    fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/maven")
+            .join(path),
+    )
+    .unwrap()
+}
+
+#[rstest]
+#[case::project_version(&[], "0.1.0-pre.1", false)]
+#[case::with_parent(&["--include-parent"], "0.1.0-pre.1", true)]
+fn test_apply_maven_sets_version_of_single_pom(
+    mut repo: ConfiguredTestRepo,
+    #[case] args: &[&str],
+    #[case] expected: &str,
+    #[case] parent: bool,
+) {
+    // Outside the repository, so the version is not affected by uncommitted changes
+    let directory = tempfile::tempdir().unwrap();
+    copy_fixture("single", directory.path());
+    let pom = directory.path().join("pom.xml");
+
+    let output = repo
+        .cmd
+        .args(["apply", "maven", "--pom"])
+        .arg(&pom)
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let expected_version = format!("<version>{expected}</version>");
+    let mut expected_pom = fixture("single/pom.xml").replace(FIXTURE_VERSION, &expected_version);
+    if parent {
+        expected_pom = expected_pom.replacen(PARENT_VERSION, &expected_version, 1);
+    }
+    assert_eq!(fs::read_to_string(&pom).unwrap(), expected_pom);
+}
+
+#[rstest]
+fn test_apply_maven_resolves_pom_against_work_tree_if_path_names_a_file(
+    mut repo: ConfiguredTestRepo,
+) {
+    let work_tree = repo.inner.config.path.clone();
+    copy_fixture("single", &work_tree);
+    let pom = work_tree.join("pom.xml");
+    let version = cmd().current_dir(&work_tree).env_clear().output().unwrap();
+    let sem_ver = serde_json::from_slice::<GitVersion>(&version.stdout)
+        .unwrap()
+        .sem_ver;
+    let elsewhere = tempfile::tempdir().unwrap();
+
+    let output = repo
+        .cmd
+        .current_dir(elsewhere.path())
+        .arg("--path")
+        .arg(&pom)
+        .args(["apply", "maven"])
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(&pom).unwrap(),
+        fixture("single/pom.xml")
+            .replace(FIXTURE_VERSION, &format!("<version>{sem_ver}</version>"))
+    );
+}
+
+#[rstest]
+fn test_apply_maven_sets_snapshot_version_if_snapshot_is_enabled(mut repo: ConfiguredTestRepo) {
+    let directory = tempfile::tempdir().unwrap();
+    copy_fixture("single", directory.path());
+    let pom = directory.path().join("pom.xml");
+
+    let output = repo
+        .cmd
+        .args(["--snapshot", "true", "apply", "maven", "--pom"])
+        .arg(&pom)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(&pom).unwrap(),
+        fixture("single/pom.xml").replace(FIXTURE_VERSION, "<version>0.1.0-SNAPSHOT</version>")
+    );
+}
+
+#[rstest]
+fn test_apply_maven_sets_versions_of_modules(mut repo: ConfiguredTestRepo) {
+    let directory = tempfile::tempdir().unwrap();
+    copy_fixture("reactor", directory.path());
+
+    let output = repo
+        .cmd
+        .args(["apply", "maven", "--modules", "--include-parent", "--pom"])
+        .arg(directory.path().join("pom.xml"))
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 3);
+    for pom in ["pom.xml", "api/pom.xml", "app/pom.xml"] {
+        assert_eq!(
+            fs::read_to_string(directory.path().join(pom)).unwrap(),
+            fixture(&format!("reactor/{pom}"))
+                .replace(FIXTURE_VERSION, "<version>0.1.0-pre.1</version>"),
+            "{pom}"
+        );
+    }
+}
+
+#[rstest]
+fn test_apply_maven_leaves_modules_inheriting_the_version_untouched(mut repo: ConfiguredTestRepo) {
+    let directory = tempfile::tempdir().unwrap();
+    copy_fixture("reactor", directory.path());
+
+    let output = repo
+        .cmd
+        .args(["apply", "maven", "--modules", "--pom"])
+        .arg(directory.path().join("pom.xml"))
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for pom in ["api/pom.xml", "app/pom.xml"] {
+        assert_eq!(
+            fs::read_to_string(directory.path().join(pom)).unwrap(),
+            fixture(&format!("reactor/{pom}")),
+            "{pom}"
+        );
+    }
+}
+
+#[rstest]
+fn test_apply_maven_fails_for_missing_pom(mut repo: ConfiguredTestRepo) {
+    let directory = tempfile::tempdir().unwrap();
+
+    let output = repo
+        .cmd
+        .args(["apply", "maven", "--pom"])
+        .arg(directory.path().join("pom.xml"))
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Could not read pom"));
+}
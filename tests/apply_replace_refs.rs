@@ -0,0 +1,98 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo() -> TestRepo {
+    let repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.commit("0.1.0-pre.1");
+    repo
+}
+
+// Cuts the history below the given commit, as done when archiving old history
+fn graft_without_parents(repo: &TestRepo, sha: &str) {
+    repo.execute(&["replace", "--graft", sha], &format!("graft {sha}"));
+}
+
+#[rstest]
+fn test_replace_refs_are_ignored_by_default(repo: TestRepo) {
+    repo.commit("0.1.0-pre.2");
+    let (sha, _) = repo.commit("0.1.0-pre.3");
+    graft_without_parents(&repo, &sha);
+    repo.commit("0.1.0-pre.4");
+
+    repo.assert().full_sem_ver("0.1.0-pre.4");
+}
+
+#[rstest]
+fn test_replace_refs_shorten_counted_history_if_applied(mut repo: TestRepo) {
+    repo.config.apply_replace_refs = true;
+    repo.commit("0.1.0-pre.2");
+    let (sha, _) = repo.commit("0.1.0-pre.3");
+    graft_without_parents(&repo, &sha);
+    repo.commit("0.1.0-pre.2");
+
+    repo.assert().full_sem_ver("0.1.0-pre.2");
+}
+
+#[rstest]
+#[case::ignored(false, "0.1.0-pre.6")]
+#[case::applied(true, "0.1.0-pre.1")]
+fn test_replace_refs_hide_tags_of_cut_history_if_applied(
+    mut repo: TestRepo,
+    #[case] apply_replace_refs: bool,
+    #[case] expected: &str,
+) {
+    repo.config.continuous_delivery = true;
+    repo.config.apply_replace_refs = apply_replace_refs;
+    repo.tag("v0.1.0-pre.5");
+    repo.commit("0.1.0-pre.6");
+    let (sha, _) = repo.commit("0.1.0-pre.6");
+    graft_without_parents(&repo, &sha);
+    repo.commit(expected);
+
+    repo.assert().full_sem_ver(expected);
+}
+
+#[rstest]
+#[case::ignored(false, "1.1.0-pre.3")]
+#[case::applied(true, "1.0.1-pre.2")]
+fn test_replace_refs_hide_bumps_of_cut_history_if_applied(
+    mut repo: TestRepo,
+    #[case] apply_replace_refs: bool,
+    #[case] expected: &str,
+) {
+    repo.config.commit_message_incrementing = "Enabled".to_string();
+    repo.config.apply_replace_refs = apply_replace_refs;
+    repo.tag("v1.0.0");
+    repo.commit("feat: archived feature");
+    let (sha, _) = repo.commit("fix: first fix after the cut");
+    graft_without_parents(&repo, &sha);
+    repo.commit("fix: second fix after the cut");
+
+    repo.assert().full_sem_ver(expected);
+}
+
+#[rstest]
+#[case::ignored(false, "1.1.0-pre.3", 3)]
+#[case::applied(true, "1.1.0-pre.2", 2)]
+fn test_version_source_in_cut_history_counts_like_unrelated_history(
+    mut repo: TestRepo,
+    #[case] apply_replace_refs: bool,
+    #[case] expected: &str,
+    #[case] commits_since_version_source: u64,
+) {
+    repo.config.apply_replace_refs = apply_replace_refs;
+    let (source, _) = repo.tag("v1.0.0");
+    repo.commit("1.1.0-pre.1");
+    let (sha, _) = repo.commit("1.1.0-pre.2");
+    graft_without_parents(&repo, &sha);
+    repo.commit(expected);
+
+    // Without a common commit, all commits reachable from HEAD count, as for an orphan branch
+    repo.assert()
+        .full_sem_ver(expected)
+        .version_source_sha(&source)
+        .commits_since_version_source(commits_since_version_source);
+}
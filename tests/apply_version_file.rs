@@ -0,0 +1,132 @@
+mod cli;
+mod common;
+
+use crate::cli::{ConfiguredTestRepo, repo};
+use rstest::rstest;
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+#[rstest]
+#[case::default_variable(&[], "0.1.0-pre.1\n")]
+#[case::custom_variable(&["--variable", "MajorMinorPatch"], "0.1.0\n")]
+#[case::numeric_variable(&["--variable", "PreReleaseNumber"], "1\n")]
+fn test_apply_version_file_creates_file(
+    mut repo: ConfiguredTestRepo,
+    #[case] args: &[&str],
+    #[case] expected: &str,
+) {
+    // Outside the repository, so the version is not affected by uncommitted changes
+    let directory = tempfile::tempdir().unwrap();
+    let file = directory.path().join("VERSION");
+
+    let output = repo
+        .cmd
+        .args(["apply", "version-file", "--path"])
+        .arg(&file)
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(fs::read_to_string(&file).unwrap(), expected);
+}
+
+#[rstest]
+#[case::rewrite(&[])]
+#[case::check(&["--check"])]
+fn test_apply_version_file_leaves_up_to_date_file_untouched(
+    mut repo: ConfiguredTestRepo,
+    #[case] args: &[&str],
+) {
+    let directory = tempfile::tempdir().unwrap();
+    let file = directory.path().join("VERSION");
+    fs::write(&file, "0.1.0-pre.1\n").unwrap();
+    let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    fs::File::options()
+        .write(true)
+        .open(&file)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+
+    let output = repo
+        .cmd
+        .args(["apply", "version-file", "--path"])
+        .arg(&file)
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(output.stdout.is_empty());
+    assert_eq!(fs::metadata(&file).unwrap().modified().unwrap(), modified);
+}
+
+#[rstest]
+#[case::outdated_version("0.0.9\n")]
+#[case::missing_newline("0.1.0-pre.1")]
+fn test_apply_version_file_check_fails_for_stale_file(
+    mut repo: ConfiguredTestRepo,
+    #[case] content: &str,
+) {
+    let directory = tempfile::tempdir().unwrap();
+    let file = directory.path().join("VERSION");
+    fs::write(&file, content).unwrap();
+
+    let output = repo
+        .cmd
+        .args(["apply", "version-file", "--check", "--path"])
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is stale, expected 0.1.0-pre.1"));
+    assert_eq!(fs::read_to_string(&file).unwrap(), content);
+}
+
+#[rstest]
+fn test_apply_version_file_rewrites_stale_file(mut repo: ConfiguredTestRepo) {
+    let directory = tempfile::tempdir().unwrap();
+    let file = directory.path().join("VERSION");
+    fs::write(&file, "0.0.9\n").unwrap();
+
+    let output = repo
+        .cmd
+        .args(["apply", "version-file", "--path"])
+        .arg(&file)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(&file).unwrap(), "0.1.0-pre.1\n");
+}
+
+#[rstest]
+fn test_apply_version_file_fails_for_unknown_variable(mut repo: ConfiguredTestRepo) {
+    let directory = tempfile::tempdir().unwrap();
+
+    let output = repo
+        .cmd
+        .args([
+            "apply",
+            "version-file",
+            "--variable",
+            "FullSemver",
+            "--path",
+        ])
+        .arg(directory.path().join("VERSION"))
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains(r#"Invalid value "FullSemver""#));
+}
@@ -2,6 +2,8 @@ mod cli;
 mod common;
 
 use crate::cli::{COMMIT_DATE, ConfiguredTestRepo, cmd, repo};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use git_versioner::config::{ConfigurationFile, DefaultConfig};
 use insta::assert_snapshot;
 use insta_cmd::assert_cmd_snapshot;
@@ -38,8 +40,10 @@ macro_rules! with_masked_unpredictable_values {
     ($($block:tt)*) => {
         insta::with_settings!({
             filters => vec![
+                (r#""(RepositoryPath|GitDir)": ".*""#, r#""$1": "[path]""#), // Temporary directory
                 (r"\b[[:xdigit:]]{40}\b", "########################################"), // SHA1
                 (r"\b[[:xdigit:]]{7}\b", "#######"), // Short SHA1
+                (r"\b\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}Z", "####-##-##T##:##:##Z"), // Build time
             ]
         }, {
             $($block)*
@@ -106,6 +110,14 @@ fn test_output_from_tag_checked_out(mut repo: ConfiguredTestRepo) {
     }
 }
 
+#[rstest]
+fn test_output_from_increment_preview(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("0.1.0");
+    repo.inner.commit_at("0.2.0-pre.1", COMMIT_DATE);
+
+    assert_cmd_snapshot!(repo.cmd.args(["--increment-preview"]));
+}
+
 #[rstest]
 fn test_environment_variable_output_in_github_context(mut repo: ConfiguredTestRepo) {
     let github_output = tempfile::NamedTempFile::new().unwrap();
@@ -126,6 +138,62 @@ fn test_environment_variable_output_in_github_context(mut repo: ConfiguredTestRe
     }
 }
 
+#[rstest]
+fn test_environment_variable_output_in_gitea_context(mut repo: ConfiguredTestRepo) {
+    let github_output = tempfile::NamedTempFile::new().unwrap();
+
+    let output = repo
+        .cmd
+        .env("CI", "true")
+        .env("GITEA_ACTIONS", "true")
+        .env("GITHUB_OUTPUT", github_output.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let github_output = std::fs::read_to_string(github_output.path()).unwrap();
+
+    // Gitea parses the same format as GitHub, so the output must not differ
+    with_masked_unpredictable_values! {
+        assert_snapshot!("environment_variable_output_in_github_context", github_output);
+    }
+}
+
+#[rstest]
+fn test_github_actions_notice_annotation(mut repo: ConfiguredTestRepo) {
+    repo.inner.branch("feature/50%-off");
+    repo.inner.commit_at("0.1.0-50--off.1", COMMIT_DATE);
+
+    let output = repo
+        .cmd
+        .env("CI", "true")
+        .env("GITHUB_ACTIONS", "true")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    with_masked_unpredictable_values! {
+        assert_snapshot!(stdout);
+    }
+}
+
+#[rstest]
+fn test_github_actions_notice_annotation_can_be_disabled(mut repo: ConfiguredTestRepo) {
+    let output = repo
+        .cmd
+        .env("CI", "true")
+        .env("GITHUB_ACTIONS", "true")
+        .args(["--annotations", "false"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("::notice"), "{stdout}");
+}
+
 #[rstest]
 fn test_environment_variable_output_in_gitlab_context(mut repo: ConfiguredTestRepo) {
     let gitlab_env = tempfile::NamedTempFile::new().unwrap();
@@ -146,6 +214,104 @@ fn test_environment_variable_output_in_gitlab_context(mut repo: ConfiguredTestRe
     }
 }
 
+#[rstest]
+fn test_environment_variable_output_in_bamboo_context(mut repo: ConfiguredTestRepo) {
+    let inject_file = tempfile::NamedTempFile::new().unwrap();
+
+    let output = repo
+        .cmd
+        .env("bamboo_buildKey", "PROJ-PLAN-JOB1")
+        .env("BAMBOO_INJECT_FILE", inject_file.path())
+        .args([
+            "--assembly-informational-format",
+            "{FullSemVer} built=nightly",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let bamboo_properties = std::fs::read_to_string(inject_file.path()).unwrap();
+
+    with_masked_unpredictable_values! {
+        assert_snapshot!(bamboo_properties);
+    }
+}
+
+#[rstest]
+fn test_environment_variable_output_to_file_from_configured_variable(mut repo: ConfiguredTestRepo) {
+    let output_file = tempfile::NamedTempFile::new().unwrap();
+    let gitlab_env = tempfile::NamedTempFile::new().unwrap();
+    repo.config_file.output_env_file_var = Some("CUSTOM_OUTPUT_FILE".to_string());
+    // Outside the repository, so it does not count as an uncommitted change
+    let config_file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+    std::fs::write(config_file.path(), repo.serialize_config("toml").unwrap()).unwrap();
+
+    let output = repo
+        .cmd
+        .arg("--config")
+        .arg(config_file.path())
+        .env("CUSTOM_OUTPUT_FILE", output_file.path())
+        .env("CI", "true")
+        .env("GITLAB_CI", "true")
+        .env("GITLAB_ENV", gitlab_env.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output_file = std::fs::read_to_string(output_file.path()).unwrap();
+    let gitlab_env = std::fs::read_to_string(gitlab_env.path()).unwrap();
+
+    // Both use the same format, so the output must not differ
+    with_masked_unpredictable_values! {
+        assert_snapshot!("environment_variable_output_in_gitlab_context", output_file);
+        assert_snapshot!("environment_variable_output_in_gitlab_context", gitlab_env);
+    }
+}
+
+#[rstest]
+#[case::github("env-github", "environment_variable_output_in_github_context")]
+#[case::gitlab("env-gitlab", "environment_variable_output_in_gitlab_context")]
+fn test_environment_variable_output_printed_locally(
+    mut repo: ConfiguredTestRepo,
+    #[case] format: &str,
+    #[case] snapshot: &str,
+) {
+    let output = repo.cmd.args(["--output", format]).output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Must not differ from what the exporter writes in the respective CI context
+    with_masked_unpredictable_values! {
+        assert_snapshot!(snapshot, stdout);
+    }
+}
+
+#[rstest]
+fn test_docker_args_and_labels_files(mut repo: ConfiguredTestRepo) {
+    // Outside the repository, so they do not count as uncommitted changes
+    let docker_args = tempfile::NamedTempFile::new().unwrap();
+    let docker_labels = tempfile::NamedTempFile::new().unwrap();
+
+    let output = repo
+        .cmd
+        .arg("--docker-args-file")
+        .arg(docker_args.path())
+        .arg("--docker-labels-file")
+        .arg(docker_labels.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let docker_args = std::fs::read_to_string(docker_args.path()).unwrap();
+    let docker_labels = std::fs::read_to_string(docker_labels.path()).unwrap();
+
+    with_masked_unpredictable_values! {
+        assert_snapshot!("docker_args_file", docker_args);
+        assert_snapshot!("docker_labels_file", docker_labels);
+    }
+}
+
 #[rstest]
 fn test_environment_variable_output_in_teamcity_context(mut repo: ConfiguredTestRepo) {
     let output = repo
@@ -168,6 +334,162 @@ fn test_environment_variable_output_in_teamcity_context(mut repo: ConfiguredTest
     }
 }
 
+#[rstest]
+fn test_teamcity_system_parameters_can_be_disabled(mut repo: ConfiguredTestRepo) {
+    let output = repo
+        .cmd
+        .env("CI", "true")
+        .env("TEAMCITY_VERSION", "2024.1")
+        .args(["--teamcity-system-parameters", "false"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let teamcity_output = stdout
+        .lines()
+        .filter(|line| line.starts_with("##teamcity"))
+        .collect::<Vec<_>>();
+    assert!(!teamcity_output.is_empty());
+    for line in teamcity_output {
+        assert!(
+            line.starts_with("##teamcity[setParameter name='GitVersion."),
+            "{line}"
+        );
+    }
+}
+
+#[rstest]
+fn test_teamcity_parameters_use_custom_prefix(mut repo: ConfiguredTestRepo) {
+    let output = repo
+        .cmd
+        .env("CI", "true")
+        .env("TEAMCITY_VERSION", "2024.1")
+        .args(["--teamcity-parameter-prefix", "Version"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("##teamcity[setParameter name='Version.FullSemVer' value='0.1.0-pre.1']")
+    );
+    assert!(
+        stdout.contains(
+            "##teamcity[setParameter name='system.Version.FullSemVer' value='0.1.0-pre.1']"
+        )
+    );
+    assert!(!stdout.contains("GitVersion."), "{stdout}");
+}
+
+#[rstest]
+fn test_github_output_is_restricted_to_export_variables(mut repo: ConfiguredTestRepo) {
+    let github_output = tempfile::NamedTempFile::new().unwrap();
+    repo.config_file.export_variables = Some(vec![
+        "FullSemVer".to_string(),
+        "BranchName".to_string(),
+        "Major".to_string(),
+    ]);
+    let config_file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+    std::fs::write(config_file.path(), repo.serialize_config("toml").unwrap()).unwrap();
+
+    let output = repo
+        .cmd
+        .arg("--config")
+        .arg(config_file.path())
+        .env("CI", "true")
+        .env("GITHUB_ACTIONS", "true")
+        .env("GITHUB_OUTPUT", github_output.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let github_output = std::fs::read_to_string(github_output.path()).unwrap();
+    assert_eq!(
+        github_output,
+        "GitVersion_BranchName=trunk\n\
+         branchName=trunk\n\
+         GitVersion_FullSemVer=0.1.0-pre.1\n\
+         fullSemVer=0.1.0-pre.1\n\
+         GitVersion_Major=0\n\
+         major=0\n"
+    );
+}
+
+#[rstest]
+fn test_teamcity_output_is_restricted_to_export_variables(mut repo: ConfiguredTestRepo) {
+    let output = repo
+        .cmd
+        .env("CI", "true")
+        .env("TEAMCITY_VERSION", "2024.1")
+        .args(["--teamcity-system-parameters", "false"])
+        .args(["--export-variables", "FullSemVer"])
+        .args(["--export-variables", "BranchName"])
+        .args(["--export-variables", "Major"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let teamcity_output = stdout
+        .lines()
+        .filter(|line| line.starts_with("##teamcity"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        teamcity_output,
+        [
+            "##teamcity[setParameter name='GitVersion.BranchName' value='trunk']",
+            "##teamcity[setParameter name='GitVersion.FullSemVer' value='0.1.0-pre.1']",
+            "##teamcity[setParameter name='GitVersion.Major' value='0']",
+        ]
+    );
+}
+
+#[rstest]
+fn test_unknown_export_variable_is_rejected_with_suggestion(mut repo: ConfiguredTestRepo) {
+    let output = repo
+        .cmd
+        .args(["--export-variables", "FullSemver"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(
+            r#"Invalid value "FullSemver" for ExportVariables. Did you mean "FullSemVer"?"#
+        ),
+        "{stderr}"
+    );
+}
+
+#[rstest]
+fn test_environment_variable_output_in_octopus_context(mut repo: ConfiguredTestRepo) {
+    let output = repo
+        .cmd
+        .env("OCTOPUS_HOME", "/etc/octopus")
+        .args([
+            "--assembly-informational-format",
+            "{FullSemVer} (nightly build)",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // Encoded values of hashes and dates cannot be masked, so only stable variables are compared
+    let names = ["BranchName", "FullSemVer", "InformationalVersion"]
+        .map(|name| format!("name='{}'", BASE64.encode(format!("GitVersion.{name}"))));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let octopus_output = stdout
+        .lines()
+        .filter(|line| line.starts_with("##octopus"))
+        .filter(|line| names.iter().any(|name| line.contains(name.as_str())))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_snapshot!(octopus_output);
+}
+
 #[rstest]
 fn test_output_from_show_config(mut repo: ConfiguredTestRepo) {
     insta::with_settings!({filters => vec![
@@ -176,3 +498,24 @@ fn test_output_from_show_config(mut repo: ConfiguredTestRepo) {
         assert_cmd_snapshot!(repo.cmd.args(["--show-config"]));
     });
 }
+
+#[rstest]
+fn test_output_from_plan(mut repo: ConfiguredTestRepo) {
+    commit_release_candidates(&repo);
+
+    assert_cmd_snapshot!(repo.cmd.args(["plan", "--output", "json"]));
+}
+
+#[rstest]
+fn test_human_output_from_plan(mut repo: ConfiguredTestRepo) {
+    commit_release_candidates(&repo);
+
+    assert_cmd_snapshot!(repo.cmd.args(["plan"]));
+}
+
+fn commit_release_candidates(repo: &ConfiguredTestRepo) {
+    repo.inner.tag("0.1.0");
+    repo.inner.commit_at("fix: repair parser", COMMIT_DATE);
+    repo.inner.commit_at("feat: add option", COMMIT_DATE);
+    repo.inner.commit_at("feat!: drop legacy api", COMMIT_DATE);
+}
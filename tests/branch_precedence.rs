@@ -0,0 +1,49 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+const OVERLAPPING_FEATURE_BRANCH: &str = r"^(features?|releases?)/(?<BranchName>.+)$";
+
+#[fixture]
+fn repo(#[default(MAIN_BRANCH)] main_branch: &str) -> TestRepo {
+    let mut repo = TestRepo::initialize(main_branch);
+    repo.config.commit_message_incrementing = "Disabled".to_string();
+    repo.config.feature_branch = OVERLAPPING_FEATURE_BRANCH.into();
+    repo.commit("0.1.0-pre.1");
+    repo.tag("v1.0.0");
+    repo
+}
+
+#[rstest]
+#[should_panic(
+    expected = r#"Invalid value "main,hotfix" for BranchPrecedence. Should be a comma-separated order of "main", "release" and "feature"."#
+)]
+fn test_providing_unknown_branch_category_panics(mut repo: TestRepo) {
+    repo.config.branch_precedence = "main,hotfix".to_string();
+    repo.assert(); // panics
+}
+
+#[rstest]
+#[case::release_before_feature("main,release,feature", "1.0.1-pre.1")]
+#[case::feature_before_release("main,feature,release", "1.1.0-1-0-0.1")]
+#[case::only_feature_given("feature", "1.1.0-1-0-0.1")]
+fn test_branch_precedence_classifies_branches_matching_several_patterns(
+    mut repo: TestRepo,
+    #[case] precedence: &str,
+    #[case] expected: &str,
+) {
+    repo.config.branch_precedence = precedence.to_string();
+    repo.branch("release/1.0.0");
+
+    repo.commit_and_assert(expected)
+        .branch_name("release/1.0.0");
+}
+
+#[rstest]
+fn test_branch_precedence_does_not_affect_branches_matching_a_single_pattern(mut repo: TestRepo) {
+    repo.config.branch_precedence = "feature,release,main".to_string();
+    repo.branch("feature/my-feature");
+
+    repo.commit_and_assert("1.1.0-my-feature.1");
+}
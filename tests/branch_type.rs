@@ -0,0 +1,57 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use git_versioner::{BranchType, GitVersioner, GitVersionerConfig};
+use rstest::{fixture, rstest};
+use semver::Version;
+
+#[fixture]
+fn repo() -> TestRepo {
+    let repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.commit("0.1.0-pre.1");
+    repo
+}
+
+fn versioner_for(repo: &TestRepo) -> GitVersioner {
+    GitVersioner::new(&GitVersionerConfig::new(&repo.config).unwrap()).unwrap()
+}
+
+#[rstest]
+#[case::trunk("main", BranchType::Trunk)]
+#[case::release("release/1.2", BranchType::Release(Version::new(1, 2, 0)))]
+#[case::release_with_full_version("releases-2.0.1", BranchType::Release(Version::new(2, 0, 1)))]
+#[case::feature("feature/login", BranchType::Feature("login".to_string()))]
+#[case::release_without_version("release/next", BranchType::Other("release/next".to_string()))]
+#[case::other("bugfix/crash", BranchType::Other("bugfix/crash".to_string()))]
+fn test_branch_type_classifies_branch_names(
+    repo: TestRepo,
+    #[case] name: &str,
+    #[case] expected: BranchType,
+) {
+    assert_eq!(versioner_for(&repo).branch_type(name), expected);
+}
+
+#[rstest]
+fn test_branch_type_respects_configured_patterns(mut repo: TestRepo) {
+    repo.config.release_branch = r"^stable/(?<BranchName>.+)$".into();
+
+    let versioner = versioner_for(&repo);
+
+    assert_eq!(
+        versioner.branch_type("stable/3.1.0"),
+        BranchType::Release(Version::new(3, 1, 0))
+    );
+    assert_eq!(
+        versioner.branch_type("release/3.1.0"),
+        BranchType::Other("release/3.1.0".to_string())
+    );
+}
+
+#[rstest]
+#[case::trunk(BranchType::Trunk, "trunk")]
+#[case::release(BranchType::Release(Version::new(1, 2, 0)), "release(1.2.0)")]
+#[case::feature(BranchType::Feature("login".to_string()), "feature(login)")]
+#[case::other(BranchType::Other("bugfix/crash".to_string()), "other(bugfix/crash)")]
+fn test_branch_type_display(#[case] branch_type: BranchType, #[case] expected: &str) {
+    assert_eq!(branch_type.to_string(), expected);
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo(#[default(MAIN_BRANCH)] main_branch: &str) -> TestRepo {
+    let mut repo = TestRepo::initialize(main_branch);
+    repo.config.commit_message_incrementing = "Disabled".to_string();
+    repo.commit("0.1.0-pre.1");
+    repo.tag("v1.0.0");
+    repo.commit("1.1.0-pre.1");
+    repo
+}
+
+#[rstest]
+#[should_panic(
+    expected = r#"Invalid value "foo" for BuildMetadataMode. Should be "none", "commits", "sha" or "commits.sha"."#
+)]
+fn test_providing_unknown_build_metadata_mode_panics(mut repo: TestRepo) {
+    repo.config.build_metadata_mode = "foo".to_string();
+    repo.assert(); // panics
+}
+
+#[rstest]
+#[case::none("none", "")]
+#[case::commits("commits", "2")]
+#[case::sha("sha", "Sha.{ShortSha}")]
+#[case::commits_and_sha("commits.sha", "2.Sha.{ShortSha}")]
+fn test_build_metadata_mode_determines_build_metadata(
+    mut repo: TestRepo,
+    #[case] mode: &str,
+    #[case] expected: &str,
+) {
+    repo.config.build_metadata_mode = mode.to_string();
+    let (sha, _) = repo.commit("1.1.0-pre.2");
+
+    let expected = expected.replace("{ShortSha}", &sha[..7]);
+    let full_sem_ver = match expected.is_empty() {
+        true => "1.1.0-pre.2".to_string(),
+        false => format!("1.1.0-pre.2+{expected}"),
+    };
+    repo.assert()
+        .sem_ver("1.1.0-pre.2")
+        .commits_since_version_source(2)
+        .build_metadata(&expected)
+        .full_build_meta_data(&expected)
+        .full_sem_ver(&full_sem_ver)
+        .informational_version(&full_sem_ver);
+}
+
+#[rstest]
+fn test_build_metadata_mode_is_ignored_without_metadata(mut repo: TestRepo) {
+    repo.config.build_metadata_mode = "commits.sha".to_string();
+    repo.config.no_metadata = true;
+
+    repo.assert()
+        .full_sem_ver("1.1.0-pre.1")
+        .build_metadata("")
+        .full_build_meta_data("");
+}
+
+#[rstest]
+fn test_build_metadata_format_takes_precedence_over_build_metadata_mode(mut repo: TestRepo) {
+    repo.config.build_metadata_mode = "commits".to_string();
+    repo.config.build_metadata_format = "build.{CommitsSinceVersionSource}".to_string();
+
+    repo.assert().full_sem_ver("1.1.0-pre.1+build.1");
+}
+
+#[rstest]
+fn test_full_build_meta_data_contains_build_metadata_of_version_source(repo: TestRepo) {
+    repo.tag("v1.1.0+build.5");
+
+    repo.assert()
+        .full_sem_ver("1.1.0+build.5")
+        .build_metadata("build.5")
+        .full_build_meta_data("build.5")
+        .commits_since_version_source(0);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo(#[default(MAIN_BRANCH)] main_branch: &str) -> TestRepo {
+    let mut repo = TestRepo::initialize(main_branch);
+    repo.config.scheme = "CalVer".to_string();
+    repo
+}
+
+#[rstest]
+#[should_panic(expected = r#"Invalid value "foo" for Scheme. Should be "SemVer" or "CalVer"."#)]
+fn test_providing_unknown_scheme_panics(mut repo: TestRepo) {
+    repo.config.scheme = "foo".to_string();
+    repo.commit("0.1.0-pre.1");
+    repo.assert(); // panics
+}
+
+#[rstest]
+#[should_panic(
+    expected = r#"Invalid value "YYYY.WW" for CalVerFormat. Should be "<YYYY|YY>.<MM|0M>.<PATCH|MICRO>"."#
+)]
+fn test_providing_unknown_cal_ver_format_panics(mut repo: TestRepo) {
+    repo.config.cal_ver_format = "YYYY.WW".to_string();
+    repo.commit("0.1.0-pre.1");
+    repo.assert(); // panics
+}
+
+#[rstest]
+fn test_on_main_branch_version_is_derived_from_commit_date(repo: TestRepo) {
+    repo.commit_at("2024.3.0-pre.1", "2024-03-09T12:00:00Z");
+
+    repo.assert()
+        .full_sem_ver("2024.3.0-pre.1")
+        .version_source_sha("");
+}
+
+#[rstest]
+fn test_release_tag_on_head_is_the_version(repo: TestRepo) {
+    repo.commit_at("2024.3.0-pre.1", "2024-03-09T12:00:00Z");
+    repo.tag("v2024.3.0");
+
+    repo.assert().full_sem_ver("2024.3.0");
+}
+
+#[rstest]
+fn test_patch_counts_release_tags_made_in_the_same_month(repo: TestRepo) {
+    repo.commit_at("2024.3.0-pre.1", "2024-03-09T12:00:00Z");
+    repo.tag("v2024.3.0");
+    repo.commit_at("2024.3.1-pre.1", "2024-03-15T12:00:00Z");
+    let (sha, _) = repo.tag("v2024.3.1");
+    repo.commit_at("2024.3.2-pre.1", "2024-03-20T12:00:00Z");
+    repo.commit_at("2024.3.2-pre.2", "2024-03-21T12:00:00Z");
+
+    repo.assert()
+        .full_sem_ver("2024.3.2-pre.2")
+        .version_source_sha(&sha);
+}
+
+#[rstest]
+fn test_patch_restarts_in_a_new_month(repo: TestRepo) {
+    repo.commit_at("2024.3.0-pre.1", "2024-03-09T12:00:00Z");
+    repo.tag("v2024.3.0");
+    repo.commit_at("2024.4.0-pre.1", "2024-04-02T12:00:00Z");
+
+    repo.assert().full_sem_ver("2024.4.0-pre.1");
+}
+
+#[rstest]
+fn test_short_format_accepts_zero_padded_release_tags(mut repo: TestRepo) {
+    repo.config.cal_ver_format = "YY.0M.MICRO".to_string();
+    repo.commit_at("24.3.0-pre.1", "2024-03-09T12:00:00Z");
+    let (sha, _) = repo.tag("v24.03.0");
+    repo.commit_at("24.3.1-pre.1", "2024-03-20T12:00:00Z");
+
+    repo.assert()
+        .full_sem_ver("24.3.1-pre.1")
+        .version_source_sha(&sha);
+}
+
+#[rstest]
+fn test_continuous_delivery_continues_pre_release_tags(mut repo: TestRepo) {
+    repo.config.continuous_delivery = true;
+    repo.commit_at("2024.3.0-pre.1", "2024-03-09T12:00:00Z");
+    repo.tag("v2024.3.0-pre.1");
+    repo.commit_at("2024.3.0-pre.2", "2024-03-10T12:00:00Z");
+
+    repo.assert().full_sem_ver("2024.3.0-pre.2");
+}
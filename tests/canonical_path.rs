@@ -0,0 +1,35 @@
+use git_versioner::config::{canonicalize, without_verbatim_prefix};
+use rstest::rstest;
+use std::path::{Path, PathBuf};
+
+#[rstest]
+#[case(r"\\?\C:\repositories\project", r"C:\repositories\project")]
+#[case(r"\\?\UNC\server\share\project", r"\\server\share\project")]
+#[case(r"\\server\share\project", r"\\server\share\project")]
+#[case("/home/user/project", "/home/user/project")]
+fn test_that_verbatim_prefixes_are_removed(#[case] raw: &str, #[case] expected: &str) {
+    assert_eq!(
+        without_verbatim_prefix(Path::new(raw)),
+        PathBuf::from(expected)
+    );
+}
+
+#[cfg(windows)]
+#[rstest]
+fn test_that_canonicalized_paths_have_no_verbatim_prefix() {
+    let directory = tempfile::tempdir().unwrap();
+
+    let path = canonicalize(directory.path()).unwrap();
+
+    assert!(!path.to_string_lossy().starts_with(r"\\?\"));
+}
+
+#[cfg(unix)]
+#[rstest]
+fn test_that_canonicalized_paths_match_file_system_canonicalization() {
+    let directory = tempfile::tempdir().unwrap();
+
+    let path = canonicalize(directory.path()).unwrap();
+
+    assert_eq!(path, std::fs::canonicalize(directory.path()).unwrap());
+}
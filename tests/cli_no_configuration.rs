@@ -1,8 +1,14 @@
 mod cli;
 mod common;
 
-use crate::cli::{ConfiguredTestRepo, repo};
+use crate::cli::{ConfiguredTestRepo, cmd, repo};
+use crate::common::MAIN_BRANCH;
+use git_versioner::config::canonicalize;
+use git_versioner::{DetectedVersionSource, GitVersion, ReleasePlan, VersionComparison};
+use indexmap::IndexMap;
 use rstest::rstest;
+use std::fs;
+use std::path::Path;
 
 #[rstest]
 fn test_release_candidate_on_main_branch(mut repo: ConfiguredTestRepo) {
@@ -21,6 +27,181 @@ fn test_release_request_on_main_branch(mut repo: ConfiguredTestRepo) {
     repo.execute_and_verify(["--as-release"], None);
 }
 
+#[rstest]
+fn test_release_on_main_branch_without_metadata(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("0.1.0+build.5");
+    repo.inner.config.no_metadata = true;
+    repo.execute_and_verify(["--no-metadata"], None);
+
+    repo.inner
+        .assert()
+        .full_sem_ver("0.1.0")
+        .sem_ver("0.1.0")
+        .build_metadata("")
+        .full_build_meta_data("");
+}
+
+#[rstest]
+#[case::with_run_number(Some("5678"), "build.5678")]
+#[case::without_run_number(None, "build.0")]
+#[case::with_invalid_characters(Some("56/78_a"), "build.56-78-a")]
+fn test_build_metadata_format_renders_environment_variables(
+    mut repo: ConfiguredTestRepo,
+    #[case] run_number: Option<&str>,
+    #[case] expected: &str,
+) {
+    if let Some(run_number) = run_number {
+        repo.cmd.env("GITHUB_RUN_NUMBER", run_number);
+    }
+
+    let output = repo
+        .cmd
+        .args([
+            "--build-metadata-format",
+            "build.{env:GITHUB_RUN_NUMBER ?? 0}",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let actual: GitVersion = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(actual.build_metadata, expected);
+    assert_eq!(actual.full_build_meta_data, expected);
+    assert_eq!(actual.full_sem_ver, format!("0.1.0-pre.1+{expected}"));
+    assert_eq!(
+        actual.informational_version,
+        format!("0.1.0-pre.1+{expected}")
+    );
+}
+
+#[rstest]
+#[case::without_attempt(None, "")]
+#[case::first_attempt(Some("1"), "")]
+#[case::second_attempt(Some("2"), "+rebuild.2")]
+fn test_rebuild_counter_is_appended_to_build_metadata_of_repeated_builds(
+    mut repo: ConfiguredTestRepo,
+    #[case] attempt: Option<&str>,
+    #[case] expected_suffix: &str,
+) {
+    repo.inner.tag("0.1.0");
+    repo.cmd.env_remove("GITHUB_RUN_ATTEMPT");
+    if let Some(attempt) = attempt {
+        repo.cmd.env("GITHUB_RUN_ATTEMPT", attempt);
+    }
+
+    let output = repo
+        .cmd
+        .args(["--rebuild-counter-env", "GITHUB_RUN_ATTEMPT"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let actual: GitVersion = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(actual.sem_ver, "0.1.0");
+    assert_eq!(actual.full_sem_ver, format!("0.1.0{expected_suffix}"));
+    assert_eq!(
+        actual.build_metadata,
+        expected_suffix.trim_start_matches('+')
+    );
+}
+
+#[rstest]
+fn test_output_file_is_written_as_dotenv_while_stdout_stays_json(mut repo: ConfiguredTestRepo) {
+    let output_file = repo.inner.config.path.join("version.env");
+
+    let output = repo
+        .cmd
+        .args([
+            "--output-file",
+            "version.env",
+            "--output-file-format",
+            "dotenv",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let actual: GitVersion = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(actual.sem_ver, "0.1.0-pre.1");
+    let dotenv = fs::read_to_string(output_file).unwrap();
+    assert!(
+        dotenv.contains("GitVersion_SemVer=0.1.0-pre.1\n"),
+        "{dotenv}"
+    );
+    assert!(!dotenv.contains('{'), "{dotenv}");
+}
+
+#[rstest]
+fn test_output_file_is_written_as_yaml_with_same_content_as_stdout(mut repo: ConfiguredTestRepo) {
+    let output_file = repo.inner.config.path.join("version.yaml");
+
+    let output = repo
+        .cmd
+        .args([
+            "--output-file",
+            "version.yaml",
+            "--output-file-format",
+            "yaml",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let actual: GitVersion = serde_json::from_slice(&output.stdout).unwrap();
+    let from_file: GitVersion =
+        serde_saphyr::from_str(&fs::read_to_string(output_file).unwrap()).unwrap();
+    assert_eq!(from_file, actual);
+}
+
+#[rstest]
+fn test_invalid_output_file_format_fails_before_printing(mut repo: ConfiguredTestRepo) {
+    let output = repo
+        .cmd
+        .args([
+            "--output-file",
+            "version.toml",
+            "--output-file-format",
+            "toml",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr).trim_end(),
+        r#"Error: Invalid value "toml" for OutputFileFormat. Should be "json", "dotenv" or "yaml"."#
+    );
+    assert!(!repo.inner.config.path.join("version.toml").exists());
+}
+
+#[rstest]
+#[case::github_output_file("env-github", &["--output-file", "version.json"])]
+#[case::gitlab_output_file("env-gitlab", &["--output-file", "version.json"])]
+#[case::github_omit_fields("env-github", &["--omit-fields", "Sha"])]
+#[case::gitlab_omit_fields("env-gitlab", &["--omit-fields", "Sha"])]
+fn test_environment_variable_output_rejects_json_only_options(
+    mut repo: ConfiguredTestRepo,
+    #[case] format: &str,
+    #[case] args: &[&str],
+) {
+    let output = repo
+        .cmd
+        .args(["--output", format])
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr).trim_end(),
+        format!(
+            r#"Error: Output "{format}" prints the variables as exported, which OmitFields and OutputFile do not apply to. Use ExportVariables to select variables or use Output "json"."#
+        )
+    );
+}
+
 #[rstest]
 fn test_release_on_main_branch_with_custom_version_pattern(mut repo: ConfiguredTestRepo) {
     repo.inner.tag("my/v0.1.0");
@@ -29,26 +210,86 @@ fn test_release_on_main_branch_with_custom_version_pattern(mut repo: ConfiguredT
     repo.execute_and_verify(["--tag-prefix", "my/v"], None);
 }
 
+#[rstest]
+fn test_release_branch_pattern_without_branch_name_group_fails(mut repo: ConfiguredTestRepo) {
+    repo.inner.branch("stabilize/1.0.0");
+
+    let output = repo
+        .cmd
+        .args(["--release-branch", "^stabilize/.*$"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr).trim_end(),
+        r#"Error: Pattern "^stabilize/.*$" for ReleaseBranch is missing the named group "BranchName". Use a pattern like "^releases?[/-](?<BranchName>.+)$"."#
+    );
+}
+
 #[rstest]
 fn test_release_branch_with_custom_pattern(mut repo: ConfiguredTestRepo) {
     repo.inner.tag("v1.0.0");
     repo.inner.branch("custom-release/1.0.0");
     repo.inner.commit("1.0.1+1");
 
-    repo.inner.config.release_branch = "custom-release/(?<BranchName>.*)".to_string();
+    repo.inner.config.release_branch = "custom-release/(?<BranchName>.*)".into();
     repo.execute_and_verify(
         ["--release-branch", "custom-release/(?<BranchName>.*)"],
         None,
     );
 }
 
+#[rstest]
+fn test_release_branch_with_repeated_custom_patterns(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("v1.0.0");
+    repo.inner.branch("rel/1.0.0");
+    repo.inner.commit("1.0.1+1");
+
+    repo.inner.config.release_branch =
+        ["custom-release/(?<BranchName>.*)", "rel/(?<BranchName>.*)"].into();
+    repo.execute_and_verify(
+        [
+            "--release-branch",
+            "custom-release/(?<BranchName>.*)",
+            "--release-branch",
+            "rel/(?<BranchName>.*)",
+        ],
+        None,
+    );
+}
+
+#[rstest]
+fn test_show_config_lists_all_repeated_branch_patterns(mut repo: ConfiguredTestRepo) {
+    let output = repo
+        .cmd
+        .args([
+            "--show-config",
+            "--release-branch",
+            "^releases?/(?<BranchName>.+)$",
+            "--release-branch",
+            "^rel/(?<BranchName>.+)$",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            r#"ReleaseBranch = ["^releases?/(?<BranchName>.+)$", "^rel/(?<BranchName>.+)$"]"#
+        ),
+        "{stdout}"
+    );
+}
+
 #[rstest]
 fn test_feature_branch_with_custom_pattern(mut repo: ConfiguredTestRepo) {
     repo.inner.commit("0.1.0+1");
     repo.inner.branch("my-feature/feature");
     repo.inner.commit("0.1.0-feature.1");
 
-    repo.inner.config.feature_branch = "my-feature/(?<BranchName>.*)".to_string();
+    repo.inner.config.feature_branch = "my-feature/(?<BranchName>.*)".into();
     repo.execute_and_verify(["--feature-branch", "my-feature/(?<BranchName>.*)"], None);
 }
 
@@ -64,6 +305,52 @@ fn test_option_custom_repository_path(mut repo: ConfiguredTestRepo) {
     repo.execute_and_verify(["--path", &path], None);
 }
 
+#[rstest]
+fn test_option_repository_path_to_file_inside_repository(mut repo: ConfiguredTestRepo) {
+    let file = repo.inner.config.path.join("README.md");
+    fs::write(&file, "content").unwrap();
+    repo.inner.execute(&["add", "README.md"], "add README.md");
+    repo.inner.commit("0.1.0-pre.2");
+    // Git commands of the test repository cannot run inside a file, so the expectation is taken
+    // from the repository directory
+    let expected = repo.inner.assert().result;
+
+    let path = file.to_string_lossy().to_string();
+    let output = repo
+        .cmd
+        .args(["--path", &path])
+        .env_clear()
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let actual: GitVersion = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[rstest]
+fn test_output_contains_repository_path_and_git_dir(mut repo: ConfiguredTestRepo) {
+    let directory = tempfile::tempdir().unwrap();
+    let path = repo.inner.config.path.to_string_lossy().to_string();
+
+    let output = repo
+        .cmd
+        .current_dir(directory.path())
+        .args(["--path", &path])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let actual: GitVersion = serde_json::from_slice(&output.stdout).unwrap();
+    let expected = canonicalize(&repo.inner.config.path).unwrap();
+    assert_eq!(Path::new(&actual.repository_path), expected);
+    assert_eq!(Path::new(&actual.git_dir), expected.join(".git"));
+}
+
 #[rstest]
 fn test_argument_prerelease_tag(mut repo: ConfiguredTestRepo) {
     repo.inner.config.pre_release_tag = "alpha".to_string();
@@ -91,3 +378,802 @@ fn test_argument_assembly_informational_format(mut repo: ConfiguredTestRepo) {
         None,
     );
 }
+
+#[rstest]
+fn test_print_graph_writes_decorated_commit_graph_to_stderr(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("v0.1.0");
+    repo.inner.branch("feature/login");
+    repo.inner.commit("0.2.0-login.1");
+
+    let output = repo.cmd.args(["--print-graph"]).output().unwrap();
+
+    assert!(output.status.success());
+    let version: GitVersion = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(version.full_sem_ver, "0.2.0-login.1");
+    let graph = String::from_utf8_lossy(&output.stderr);
+    let lines = graph.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2, "{graph}");
+    assert!(lines[0].starts_with("* "), "{graph}");
+    assert!(
+        lines[0].ends_with("(HEAD -> feature/login) 0.2.0-login.1"),
+        "{graph}"
+    );
+    assert!(lines[1].contains("tag: v0.1.0"), "{graph}");
+    assert!(lines[1].contains(MAIN_BRANCH), "{graph}");
+    assert!(lines[1].ends_with(") 0.1.0-pre.1"), "{graph}");
+}
+
+#[rstest]
+fn test_print_graph_is_written_if_calculation_fails(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("1.2.0");
+    repo.inner.commit("1.2.1-pre.1");
+    repo.inner.tag("v1.2.0");
+
+    let output = repo
+        .cmd
+        .args(["--print-graph", "--strict-sources"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines = stderr.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3, "{stderr}");
+    assert!(lines[0].contains("tag: v1.2.0"), "{stderr}");
+    assert!(lines[1].contains("tag: 1.2.0"), "{stderr}");
+    assert!(lines[2].starts_with("Error: Version 1.2.0"), "{stderr}");
+}
+
+#[rstest]
+fn test_print_graph_is_written_for_subcommands(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("v0.1.0");
+
+    let output = repo
+        .cmd
+        .args(["--print-graph", "verify-tag", "v0.1.0"])
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let graph = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(graph.lines().count(), 1, "{graph}");
+    assert!(graph.contains("tag: v0.1.0"), "{graph}");
+}
+
+#[rstest]
+fn test_fail_on_dirty_succeeds_for_clean_working_tree(mut repo: ConfiguredTestRepo) {
+    repo.execute_and_verify(["--fail-on-dirty"], None);
+}
+
+#[rstest]
+fn test_fail_on_dirty_fails_for_untracked_file(mut repo: ConfiguredTestRepo) {
+    fs::write(repo.inner.config.path.join("untracked.txt"), "content").unwrap();
+
+    let output = repo.cmd.args(["--fail-on-dirty"]).output().unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Working tree has 1 uncommitted change(s)"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("Commit or stash them"), "{stderr}");
+}
+
+#[rstest]
+fn test_uncommitted_changes_counts_untracked_files(mut repo: ConfiguredTestRepo) {
+    fs::write(repo.inner.config.path.join("untracked.txt"), "content").unwrap();
+
+    let output = repo.cmd.output().unwrap();
+
+    assert!(output.status.success());
+    let version: GitVersion = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(version.uncommitted_changes, 1);
+}
+
+#[rstest]
+fn test_gitlab_release_file_for_tagged_head_uses_tag_version(mut repo: ConfiguredTestRepo) {
+    let (sha, _) = repo.inner.tag("v0.1.0");
+    let release_file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+
+    let output = repo
+        .cmd
+        .arg("--gitlab-release-file")
+        .arg(release_file.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let release: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(release_file.path()).unwrap()).unwrap();
+    assert_eq!(release["tag_name"], "v0.1.0");
+    assert_eq!(release["name"], "Release 0.1.0");
+    assert_eq!(release["ref"], sha.as_str());
+    assert!(release["description"].as_str().unwrap().contains("0.1.0"));
+}
+
+#[rstest]
+fn test_gitlab_release_file_for_untagged_head_uses_next_release_version(
+    mut repo: ConfiguredTestRepo,
+) {
+    repo.inner.tag("v0.1.0");
+    let (sha, _) = repo.inner.commit("0.2.0-pre.1");
+    let release_file = tempfile::Builder::new().suffix(".yml").tempfile().unwrap();
+
+    let output = repo
+        .cmd
+        .arg("--gitlab-release-file")
+        .arg(release_file.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let release: serde_json::Value =
+        serde_saphyr::from_str(&fs::read_to_string(release_file.path()).unwrap()).unwrap();
+    assert_eq!(release["tag_name"], "v0.2.0");
+    assert_eq!(release["name"], "Release 0.2.0");
+    assert_eq!(release["ref"], sha.as_str());
+}
+
+#[rstest]
+fn test_all_branches_as_json_lines_prints_one_object_per_branch(mut repo: ConfiguredTestRepo) {
+    repo.inner.branch("release/1.0.0");
+    repo.inner.commit("1.0.0-pre.2");
+    repo.inner.branch("feature/login");
+    repo.inner.commit("1.0.0-login.1");
+
+    let output = repo
+        .cmd
+        .args(["--all-branches", "--output", "jsonl"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines = stdout
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    let branches = lines
+        .iter()
+        .map(|line| line["branch_name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(branches, ["feature/login", "release/1.0.0", MAIN_BRANCH]);
+    for line in &lines {
+        assert_eq!(line["version"]["BranchName"], line["branch_name"]);
+    }
+}
+
+#[rstest]
+#[case::tag_against_later_trunk_commit(("v1.0.0", "1.0.0"), (MAIN_BRANCH, "1.1.0-pre.1"), true)]
+#[case::trunk_commit_against_earlier_tag((MAIN_BRANCH, "1.1.0-pre.1"), ("v1.0.0", "1.0.0"), false)]
+fn test_compare_prints_versions_of_both_references_and_their_ordering(
+    mut repo: ConfiguredTestRepo,
+    #[case] a: (&str, &str),
+    #[case] b: (&str, &str),
+    #[case] b_is_higher: bool,
+) {
+    repo.inner.tag("v1.0.0");
+    repo.inner.commit("1.1.0-pre.1");
+
+    let output = repo.cmd.args(["--compare", a.0, b.0]).output().unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let comparison: VersionComparison = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        comparison,
+        VersionComparison {
+            ref_a: a.0.to_string(),
+            version_a: a.1.to_string(),
+            ref_b: b.0.to_string(),
+            version_b: b.1.to_string(),
+            b_is_higher,
+        }
+    );
+}
+
+#[rstest]
+fn test_compare_fails_for_missing_reference(mut repo: ConfiguredTestRepo) {
+    let output = repo
+        .cmd
+        .args(["--compare", MAIN_BRANCH, "v9.9.9"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains(r#"Reference "v9.9.9" does not exist"#)
+    );
+}
+
+#[rstest]
+fn test_list_version_sources_prints_tags_and_release_branches_by_version(
+    mut repo: ConfiguredTestRepo,
+) {
+    let (first, _) = repo.inner.tag("v1.0.0");
+    repo.inner.branch("release/1.0.1");
+    let (second, _) = repo.inner.commit("1.0.1-pre.1");
+    repo.inner.tag("v1.0.1-rc.1");
+    repo.inner.checkout(MAIN_BRANCH);
+    let (third, _) = repo.inner.commit("1.1.0-pre.1");
+    repo.inner.tag("v0.9.0");
+    repo.inner.branch("releases/2.0.0");
+    repo.inner.tag("not-a-version");
+
+    let output = repo.cmd.arg("--list-version-sources").output().unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let sources: Vec<DetectedVersionSource> = serde_json::from_slice(&output.stdout).unwrap();
+    let source = |version: &str, sha: &str, is_tag: bool| DetectedVersionSource {
+        version: version.to_string(),
+        short_sha: sha[..7].to_string(),
+        is_tag,
+    };
+    assert_eq!(
+        sources,
+        vec![
+            source("0.9.0", &third, true),
+            source("1.0.0", &first, true),
+            source("1.0.1-rc.1", &second, true),
+            source("1.0.1", &second, false),
+            source("2.0.0", &third, false),
+        ]
+    );
+}
+
+#[rstest]
+#[case::default_template(&[], "Release 0.1.0-pre.1")]
+#[case::custom_template(
+    &["--tag-message-template", "Release {FullSemVer} ({ShortSha})"],
+    "Release 0.1.0-pre.1 ({ShortSha})"
+)]
+fn test_create_tag_creates_annotated_tag_with_rendered_message(
+    mut repo: ConfiguredTestRepo,
+    #[case] args: &[&str],
+    #[case] expected_message: &str,
+) {
+    let output = repo.cmd.arg("--create-tag").args(args).output().unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let tag = repo.inner.execute(
+        &[
+            "tag",
+            "--list",
+            "--format=%(objecttype) %(contents:subject)",
+        ],
+        "list tags",
+    );
+    let short_sha = repo
+        .inner
+        .execute(&["rev-parse", "--short=7", "HEAD"], "short sha");
+    let short_sha = String::from_utf8_lossy(&short_sha.stdout);
+    assert_eq!(
+        String::from_utf8_lossy(&tag.stdout).trim(),
+        format!(
+            "tag {}",
+            expected_message.replace("{ShortSha}", short_sha.trim())
+        )
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Created tag \"v0.1.0-pre.1\" for version 0.1.0-pre.1\n"
+    );
+}
+
+#[rstest]
+fn test_plan_names_the_tag_created_for_the_release(mut repo: ConfiguredTestRepo) {
+    let prefixes = [
+        "--tag-prefix",
+        "(release-|[vV])?",
+        "--preferred-tag-prefix",
+        "release-",
+    ];
+    repo.inner.tag("release-1.2.0");
+    repo.inner.commit("feat: new feature");
+
+    let output = cmd()
+        .current_dir(repo.inner.path())
+        .env_clear()
+        .args(prefixes)
+        .args(["plan", "--output", "json"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let plan: ReleasePlan = serde_json::from_slice(&output.stdout).unwrap();
+
+    let output = repo
+        .cmd
+        .args(prefixes)
+        .args(["--as-release", "--create-tag"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert_eq!(plan.tag, "release-1.3.0");
+    let tags = repo
+        .inner
+        .execute(&["tag", "--points-at", "HEAD"], "list tags at HEAD");
+    assert_eq!(String::from_utf8_lossy(&tags.stdout).trim(), plan.tag);
+}
+
+#[rstest]
+fn test_create_tag_fails_for_existing_tag(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("v0.1.0-pre.1");
+
+    let output = repo.cmd.arg("--create-tag").output().unwrap();
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains(r#"Tag "v0.1.0-pre.1" already exists"#)
+    );
+}
+
+#[rstest]
+fn test_create_tag_rejects_unknown_variables_in_message_template(mut repo: ConfiguredTestRepo) {
+    let output = repo
+        .cmd
+        .args([
+            "--create-tag",
+            "--tag-message-template",
+            "Release {FullSemver}",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains(r#"Invalid value "Release {FullSemver}" for TagMessageTemplate."#)
+    );
+    let tags = repo.inner.execute(&["tag", "--list"], "list tags");
+    assert!(tags.stdout.is_empty());
+}
+
+#[rstest]
+fn test_conflicting_version_tags_are_reported_in_verbose_mode(mut repo: ConfiguredTestRepo) {
+    let (first, _) = repo.inner.tag("1.2.0");
+    let (second, _) = repo.inner.commit("1.2.1-pre.1");
+    repo.inner.tag("v1.2.0");
+
+    let output = repo.cmd.arg("--verbose").output().unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!(
+            r#"Warning: Version 1.2.0 is tagged on different commits: "1.2.0" ({}), "v1.2.0" ({})"#,
+            &first[..7],
+            &second[..7]
+        )),
+        "{stderr}"
+    );
+}
+
+#[rstest]
+fn test_strict_sources_rejects_conflicting_version_tags(mut repo: ConfiguredTestRepo) {
+    let (first, _) = repo.inner.tag("1.2.0");
+    let (second, _) = repo.inner.commit("1.2.1-pre.1");
+    repo.inner.tag("v1.2.0");
+
+    let output = repo.cmd.arg("--strict-sources").output().unwrap();
+
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr).trim_end(),
+        format!(
+            r#"Error: Version 1.2.0 is tagged on different commits: "1.2.0" ({}), "v1.2.0" ({}). Remove all but one of them or omit --strict-sources."#,
+            &first[..7],
+            &second[..7]
+        )
+    );
+}
+
+#[rstest]
+fn test_strict_sources_accepts_tags_of_same_version_on_same_commit(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("1.2.0");
+    repo.inner.tag("v1.2.0");
+
+    let output = repo.cmd.arg("--strict-sources").output().unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let version: GitVersion = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(version.full_sem_ver, "1.2.0");
+}
+
+#[rstest]
+fn test_strict_semver_rejects_assembly_version_beyond_16_bit(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("v70000.0.0");
+
+    let output = repo.cmd.args(["--strict-semver"]).output().unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("AssemblySemVer 70000.0.0.0 exceeds the maximum component value"),
+        "{stderr}"
+    );
+}
+
+#[rstest]
+fn test_strict_semver_accepts_assembly_version_within_16_bit(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("v1.2.3");
+
+    let output = repo.cmd.args(["--strict-semver"]).output().unwrap();
+
+    assert!(output.status.success());
+}
+
+#[rstest]
+fn test_weight_base_replaces_default_weight_of_pre_release_number(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("1.0.0");
+    repo.inner.commit("second commit");
+    repo.inner.config.weight_base = Some(70000);
+    repo.execute_and_verify(["--weight-base", "70000"], None);
+
+    repo.inner
+        .assert()
+        .full_sem_ver("1.1.0-pre.1")
+        .weighted_pre_release_number(70001)
+        .assembly_sem_file_ver("1.1.0.70001");
+}
+
+#[rstest]
+#[case::tag("feature/.*:tag=alpha", "1.1.0-alpha.1")]
+#[case::tag_and_increment("feature/.*:tag=alpha,increment=major", "2.0.0-alpha.1")]
+#[case::other_branches("release/.*:tag=rc", "1.1.0-login.1")]
+fn test_branch_config_overrides_matching_feature_branch(
+    mut repo: ConfiguredTestRepo,
+    #[case] branch_config: &str,
+    #[case] expected: &str,
+) {
+    repo.inner.tag("1.0.0");
+    repo.inner.branch("feature/login");
+    repo.inner.commit("feat: login");
+    repo.inner.config.branch_config = vec![branch_config.to_string()];
+    repo.execute_and_verify(["--branch-config", branch_config], None);
+
+    repo.inner.assert().full_sem_ver(expected);
+}
+
+#[rstest]
+#[case::missing_overrides("feature/.*")]
+#[case::unknown_key("feature/.*:label=alpha")]
+#[case::unknown_increment("feature/.*:increment=huge")]
+fn test_invalid_branch_config_is_rejected(
+    mut repo: ConfiguredTestRepo,
+    #[case] branch_config: &str,
+) {
+    let output = repo
+        .cmd
+        .args(["--branch-config", branch_config])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains(&format!(
+        r#"Invalid value "{branch_config}" for BranchConfig."#
+    )));
+}
+
+#[rstest]
+fn test_snapshot_replaces_pre_release_on_main_branch(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("1.0.0");
+    repo.inner.commit("second commit");
+    repo.inner.commit("third commit");
+    repo.inner.config.snapshot = true;
+    repo.execute_and_verify(["--snapshot"], None);
+
+    repo.inner
+        .assert()
+        .full_sem_ver("1.1.0-SNAPSHOT")
+        .informational_version("1.1.0-SNAPSHOT")
+        .pre_release_number(2);
+}
+
+#[rstest]
+fn test_snapshot_leaves_tagged_release_untouched(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("1.0.0");
+    repo.inner.config.snapshot = true;
+    repo.execute_and_verify(["--snapshot", "true"], None);
+
+    repo.inner
+        .assert()
+        .full_sem_ver("1.0.0")
+        .informational_version("1.0.0");
+}
+
+#[rstest]
+#[case::first_commit(0, "0.1.0-pre.0")]
+#[case::second_commit(1, "0.1.0-pre.1")]
+fn test_first_commit_counts_as_pre_release_zero_unless_incremented(
+    mut repo: ConfiguredTestRepo,
+    #[case] additional_commits: usize,
+    #[case] expected: &str,
+) {
+    for _ in 0..additional_commits {
+        repo.inner.commit("additional commit");
+    }
+    repo.inner.config.increment_on_first_commit = false;
+    repo.execute_and_verify(["--increment-on-first-commit", "false"], None);
+
+    repo.inner.assert().full_sem_ver(expected);
+}
+
+#[rstest]
+fn test_omit_fields_removes_fields_from_printed_json_and_output_file(mut repo: ConfiguredTestRepo) {
+    let output_file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+    let omitted = ["Sha", "ShortSha", "CommitDate"];
+
+    let output = repo
+        .cmd
+        .args(omitted.iter().flat_map(|field| ["--omit-fields", *field]))
+        .arg("--output-file")
+        .arg(output_file.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let mut expected = serde_json::to_value(repo.inner.assert().result).unwrap();
+    for field in omitted {
+        assert!(expected.as_object_mut().unwrap().remove(field).is_some());
+    }
+    let printed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(printed, expected);
+    let written: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(output_file.path()).unwrap()).unwrap();
+    assert_eq!(written, expected);
+}
+
+#[rstest]
+fn test_omit_fields_keeps_the_order_of_the_remaining_fields(mut repo: ConfiguredTestRepo) {
+    let omitted = ["Major", "Sha", "GitDir"];
+    let keys_of = |stdout: &[u8]| {
+        serde_json::from_slice::<IndexMap<String, serde_json::Value>>(stdout)
+            .unwrap()
+            .into_keys()
+            .collect::<Vec<_>>()
+    };
+
+    let full = repo.cmd.output().unwrap();
+    let reduced = cmd()
+        .current_dir(repo.inner.path())
+        .env_clear()
+        .args(omitted.iter().flat_map(|field| ["--omit-fields", *field]))
+        .output()
+        .unwrap();
+
+    assert!(full.status.success());
+    assert!(reduced.status.success());
+    let mut expected = keys_of(&full.stdout);
+    expected.retain(|key| !omitted.contains(&key.as_str()));
+    assert_eq!(keys_of(&reduced.stdout), expected);
+}
+
+#[rstest]
+#[case::misspelled("ShotSha", r#"Did you mean "ShortSha"?"#)]
+#[case::lower_case("sha", r#"Did you mean "Sha"?"#)]
+#[case::unrelated(
+    "Revision",
+    r#"Should only contain output variables like "FullSemVer"."#
+)]
+fn test_omit_fields_rejects_unknown_fields(
+    mut repo: ConfiguredTestRepo,
+    #[case] field: &str,
+    #[case] hint: &str,
+) {
+    let output = repo.cmd.args(["--omit-fields", field]).output().unwrap();
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr).trim_end(),
+        format!(r#"Error: Invalid value "{field}" for OmitFields. {hint}"#)
+    );
+}
+
+#[rstest]
+#[case::major_label("pull_request_labeled_major.json", "2.0.0-pre.1")]
+#[case::highest_of_several_labels("pull_request_labeled_minor_and_patch.json", "1.1.0-pre.1")]
+#[case::patch_label("pull_request_labeled_patch.json", "1.0.1-pre.1")]
+#[case::without_labels("pull_request_without_labels.json", "1.1.0-pre.1")]
+#[case::other_event("push.json", "1.1.0-pre.1")]
+#[case::malformed_event("truncated.json", "1.1.0-pre.1")]
+#[case::missing_event("missing.json", "1.1.0-pre.1")]
+fn test_pull_request_labels_determine_bump_on_main_branch(
+    mut repo: ConfiguredTestRepo,
+    #[case] event: &str,
+    #[case] expected: &str,
+) {
+    repo.inner.tag("1.0.0");
+    repo.inner.commit("fix: unlabeled");
+    let event_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/github_events");
+
+    let output = repo
+        .cmd
+        .env("GITHUB_EVENT_PATH", event_path.join(event))
+        .args(["--pr-label-bumps", "semver:major=major"])
+        .args(["--pr-label-bumps", "semver:minor=minor"])
+        .args(["--pr-label-bumps", "semver:patch=patch"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let actual: GitVersion = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(actual.full_sem_ver, expected);
+}
+
+#[rstest]
+fn test_pull_request_labels_are_ignored_unless_mapped(mut repo: ConfiguredTestRepo) {
+    repo.inner.tag("1.0.0");
+    repo.inner.commit("feat: labeled as major");
+    let event_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/github_events/pull_request_labeled_major.json");
+
+    let output = repo
+        .cmd
+        .env("GITHUB_EVENT_PATH", event_path)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let actual: GitVersion = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(actual.full_sem_ver, "1.1.0-pre.1");
+}
+
+#[rstest]
+fn test_invalid_pull_request_label_bump_is_rejected(mut repo: ConfiguredTestRepo) {
+    let output = repo
+        .cmd
+        .args(["--pr-label-bumps", "semver:major"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains(
+        r#"Invalid value "semver:major" for PrLabelBumps. Should be "<label>=<major|minor|patch>"."#
+    ));
+}
+
+#[rstest]
+#[case::main_branch(MAIN_BRANCH, "v1.1.0")]
+#[case::release_branch("release/1.2.0", "v1.2.0")]
+fn test_verify_tag_accepts_tag_stating_computed_release_version(
+    mut repo: ConfiguredTestRepo,
+    #[case] branch: &str,
+    #[case] tag: &str,
+) {
+    repo.inner.tag("1.0.0");
+    if branch != MAIN_BRANCH {
+        repo.inner.branch(branch);
+    }
+    repo.inner.commit("feat: tagged");
+    repo.inner.tag(tag);
+
+    let output = repo.cmd.args(["verify-tag", tag]).output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        format!(r#"Tag "{tag}" matches version {}"#, &tag[1..])
+    );
+}
+
+#[rstest]
+#[case::wrong_version(
+    MAIN_BRANCH,
+    "v2.0.0",
+    r#"Tag "v2.0.0" states version 2.0.0, but its commit yields version 1.1.0"#
+)]
+#[case::feature_branch(
+    "feature/login",
+    "v1.1.0",
+    "which is on neither the main branch nor a release branch"
+)]
+#[case::prefix_mismatch(
+    MAIN_BRANCH,
+    "release-1.1.0",
+    r#"Tag "release-1.1.0" does not match the tag prefix "[vV]?" followed by a version"#
+)]
+fn test_verify_tag_rejects_invalid_tag(
+    mut repo: ConfiguredTestRepo,
+    #[case] branch: &str,
+    #[case] tag: &str,
+    #[case] expected_error: &str,
+) {
+    repo.inner.tag("1.0.0");
+    if branch != MAIN_BRANCH {
+        repo.inner.branch(branch);
+    }
+    repo.inner.commit("feat: tagged");
+    repo.inner.tag(tag);
+
+    let output = repo.cmd.args(["verify-tag", tag]).output().unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(expected_error), "{stderr}");
+}
+
+#[rstest]
+fn test_verbose_output_names_commits_that_are_no_conventional_commits(
+    mut repo: ConfiguredTestRepo,
+) {
+    repo.inner.tag("1.0.0");
+    let (sha, _) = repo
+        .inner
+        .commit("Update dependencies\n\nBumps all of them");
+    repo.inner.commit("fix: conventional");
+
+    let output = repo
+        .cmd
+        .args(["--commit-message-incrementing", "Enabled", "--verbose"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!(
+            "Commit {} is no conventional commit: Update dependencies\n",
+            &sha[..7]
+        )),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("1 commit message(s) are no conventional commits"),
+        "{stderr}"
+    );
+}
+
+#[rstest]
+#[case::verbose(&["--verbose"], true)]
+#[case::quiet(&[], false)]
+fn test_skipped_four_component_version_tags_are_reported_in_verbose_mode(
+    mut repo: ConfiguredTestRepo,
+    #[case] args: &[&str],
+    #[case] reported: bool,
+) {
+    repo.inner.tag("v1.2.3.4");
+    repo.inner.tag("latest");
+
+    let output = repo.cmd.args(args).output().unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(
+        stderr.contains(
+            r#"Tag "v1.2.3.4" is skipped, "1.2.3.4" is no semantic version, --loose-tag-versions reads it as 1.2.3"#
+        ),
+        reported,
+        "{stderr}"
+    );
+    assert!(!stderr.contains("latest"), "{stderr}");
+}
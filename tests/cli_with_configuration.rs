@@ -1,9 +1,12 @@
 mod cli;
 mod common;
 
-use crate::cli::{ConfiguredTestRepo as TestRepo, repo};
+use crate::cli::{ConfiguredTestRepo as TestRepo, cmd, repo};
+use git_versioner::GitVersion;
 use rstest::rstest;
 use rstest_reuse::{apply, template};
+use std::io::Write;
+use std::process::Stdio;
 
 const CUSTOM_MAIN_BRANCH: &str = "stem";
 const DEFAULT_CONFIG: &str = ".git-versioner";
@@ -39,13 +42,13 @@ fn test_that_cli_argument_overrides_configuration_of_main_branch_pattern(
 
 #[apply(default)]
 fn test_that_config_file_overrides_default_release_branch_pattern(mut repo: TestRepo, ext: &str) {
-    repo.config_file.release_branch = Some("custom-release/(?<BranchName>.*)".to_string());
+    repo.config_file.release_branch = Some("custom-release/(?<BranchName>.*)".into());
     repo.inner.commit("0.1.0+2");
     repo.inner.tag("v1.0.0");
     repo.inner.branch("custom-release/1.0.0");
     repo.inner.commit("1.0.1+1");
 
-    repo.inner.config.release_branch = "custom-release/(?<BranchName>.*)".to_string();
+    repo.inner.config.release_branch = "custom-release/(?<BranchName>.*)".into();
     repo.execute_and_verify([], Some((DEFAULT_CONFIG, ext)));
 }
 
@@ -54,13 +57,13 @@ fn test_that_cli_argument_overrides_configuration_of_release_branch_pattern(
     mut repo: TestRepo,
     ext: &str,
 ) {
-    repo.config_file.release_branch = Some("whatever-release/(?<BranchName>.*)".to_string());
+    repo.config_file.release_branch = Some("whatever-release/(?<BranchName>.*)".into());
     repo.inner.commit("0.1.0+1");
     repo.inner.tag("v1.0.0");
     repo.inner.branch("custom-release/1.0.0");
     repo.inner.commit("1.0.1+1");
 
-    repo.inner.config.release_branch = "custom-release/(?<BranchName>.*)".to_string();
+    repo.inner.config.release_branch = "custom-release/(?<BranchName>.*)".into();
     repo.execute_and_verify(
         ["--release-branch", "custom-release/(?<BranchName>.*)"],
         Some((DEFAULT_CONFIG, ext)),
@@ -69,12 +72,12 @@ fn test_that_cli_argument_overrides_configuration_of_release_branch_pattern(
 
 #[apply(default)]
 fn test_that_config_file_overrides_default_feature_branch_pattern(mut repo: TestRepo, ext: &str) {
-    repo.config_file.feature_branch = Some("my-feature/(?<BranchName>.*)".to_string());
+    repo.config_file.feature_branch = Some("my-feature/(?<BranchName>.*)".into());
     repo.inner.commit("0.1.0+1");
     repo.inner.branch("my-feature/feature");
     repo.inner.commit("0.1.0-feature.1");
 
-    repo.inner.config.feature_branch = "my-feature/(?<BranchName>.*)".to_string();
+    repo.inner.config.feature_branch = "my-feature/(?<BranchName>.*)".into();
     repo.execute_and_verify([], Some((DEFAULT_CONFIG, ext)));
 }
 
@@ -83,18 +86,47 @@ fn test_that_cli_argument_overrides_configuration_of_feature_branch_pattern(
     mut repo: TestRepo,
     ext: &str,
 ) {
-    repo.config_file.feature_branch = Some("whatever-feature/(?<BranchName>.*)".to_string());
+    repo.config_file.feature_branch = Some("whatever-feature/(?<BranchName>.*)".into());
     repo.inner.commit("0.1.0+1");
     repo.inner.branch("my-feature/feature");
     repo.inner.commit("0.1.0-feature.1");
 
-    repo.inner.config.feature_branch = "my-feature/(?<BranchName>.*)".to_string();
+    repo.inner.config.feature_branch = "my-feature/(?<BranchName>.*)".into();
     repo.execute_and_verify(
         ["--feature-branch", "my-feature/(?<BranchName>.*)"],
         Some((DEFAULT_CONFIG, ext)),
     );
 }
 
+#[apply(default)]
+fn test_that_config_file_overrides_default_case_insensitive_branches(
+    mut repo: TestRepo,
+    ext: &str,
+) {
+    repo.config_file.case_insensitive_branches = Some(true);
+    repo.inner.branch("Release/1.0.0");
+    repo.inner.commit("1.0.0+1");
+
+    repo.inner.config.case_insensitive_branches = true;
+    repo.execute_and_verify([], Some((DEFAULT_CONFIG, ext)));
+}
+
+#[apply(default)]
+fn test_that_cli_argument_overrides_configuration_of_case_insensitive_branches(
+    mut repo: TestRepo,
+    ext: &str,
+) {
+    repo.config_file.case_insensitive_branches = Some(true);
+    repo.inner.branch("Release/1.0.0");
+    repo.inner.commit("1.0.0+1");
+
+    repo.inner.config.case_insensitive_branches = false;
+    repo.execute_and_verify(
+        ["--case-insensitive-branches", "false"],
+        Some((DEFAULT_CONFIG, ext)),
+    );
+}
+
 #[apply(default)]
 fn test_that_config_file_overrides_default_version_pattern(mut repo: TestRepo, ext: &str) {
     repo.config_file.tag_prefix = Some("my/v".to_string());
@@ -192,3 +224,166 @@ fn test_that_cli_argument_overrides_configuration_of_assembly_informational_form
         Some((DEFAULT_CONFIG, ext)),
     );
 }
+
+#[rstest]
+#[case::yaml("yaml")]
+#[case::toml("toml")]
+fn test_that_configuration_is_read_from_stdin(mut repo: TestRepo, #[case] format: &str) {
+    repo.config_file.pre_release_tag = Some("alpha".to_string());
+    let content = repo.serialize_config(format).unwrap();
+
+    let mut child = repo
+        .cmd
+        .args(["--config", "-", "--config-format", format])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(content.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let actual: GitVersion = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(actual.sem_ver, "0.1.0-alpha.1");
+}
+
+#[rstest]
+fn test_that_configuration_from_stdin_requires_a_format(mut repo: TestRepo) {
+    let output = repo
+        .cmd
+        .args(["--config", "-"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Reading the configuration from stdin requires --config-format")
+    );
+}
+
+#[rstest]
+fn test_that_legacy_gitversion_file_applies_without_native_configuration(
+    #[with(CUSTOM_MAIN_BRANCH)] mut repo: TestRepo,
+) {
+    std::fs::write(
+        repo.inner.config.path.join("gitversion.yaml"),
+        r#"tag-prefix: my/v
+mode: ContinuousDelivery
+branches:
+  main:
+    regex: ^stem$
+    label: ''
+"#,
+    )
+    .unwrap();
+    repo.inner.commit("0.1.0+1");
+    repo.inner.tag("my/v1.0.0");
+    repo.inner.commit("1.1.0-pre.1");
+
+    repo.inner.config.tag_prefix = "my/v".to_string();
+    repo.inner.config.main_branch = "^stem$".to_string();
+    repo.execute_and_verify([], None);
+}
+
+#[rstest]
+fn test_that_legacy_branch_regexes_without_branch_name_group_are_skipped(mut repo: TestRepo) {
+    std::fs::write(
+        repo.inner.config.path.join("GitVersion.yml"),
+        r#"mode: ContinuousDelivery
+branches:
+  release:
+    regex: ^releases?[/-]
+  feature:
+    regex: ^features?[/-]
+"#,
+    )
+    .unwrap();
+    repo.inner.branch("release/1.0.0");
+    repo.inner.commit("1.0.0-pre.1");
+
+    repo.execute_and_verify([], None);
+
+    let output = cmd()
+        .current_dir(repo.inner.path())
+        .env_clear()
+        .arg("--verbose")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for (name, regex) in [("release", "^releases?[/-]"), ("feature", "^features?[/-]")] {
+        assert!(
+            stderr.contains(&format!(
+                r#"GitVersion setting branches.{name}.regex "{regex}" is skipped, as it has no named group "BranchName""#
+            )),
+            "{stderr}"
+        );
+    }
+}
+
+#[apply(default)]
+fn test_that_native_configuration_takes_precedence_over_legacy_gitversion_file(
+    mut repo: TestRepo,
+    ext: &str,
+) {
+    std::fs::write(
+        repo.inner.config.path.join(".gitversion.yml"),
+        "tag-prefix: other/v\n",
+    )
+    .unwrap();
+    repo.config_file.tag_prefix = Some("my/v".to_string());
+    repo.inner.commit("0.1.0+1");
+    repo.inner.tag("my/v1.0.0");
+
+    repo.inner.config.tag_prefix = "my/v".to_string();
+    repo.execute_and_verify([], Some((DEFAULT_CONFIG, ext)));
+}
+
+#[rstest]
+fn test_that_configuration_is_read_from_tool_table_of_shared_toml_file(mut repo: TestRepo) {
+    std::fs::write(
+        repo.inner.config.path.join(".git-versioner.toml"),
+        r#"[project]
+name = "service"
+
+[tool.git-versioner]
+TagPrefix = "my/v"
+
+[tool.other]
+TagPrefix = "other/v"
+"#,
+    )
+    .unwrap();
+    repo.inner.commit("0.1.0+1");
+    repo.inner.tag("my/v1.0.0");
+    repo.inner.commit("1.1.0-pre.1");
+
+    repo.inner.config.tag_prefix = "my/v".to_string();
+    repo.execute_and_verify([], None);
+}
+
+#[rstest]
+fn test_that_configuration_is_read_from_config_file_with_tool_table(mut repo: TestRepo) {
+    // Outside the repository, so it does not count as an uncommitted change
+    let pyproject = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+    std::fs::write(
+        pyproject.path(),
+        "[project]\nname = \"service\"\n\n[tool.git-versioner]\nPreReleaseTag = \"beta\"\n",
+    )
+    .unwrap();
+
+    repo.inner.config.pre_release_tag = "beta".to_string();
+    repo.execute_and_verify(["--config", pyproject.path().to_str().unwrap()], None);
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+const AUTHOR_DATE: &str = "2024-02-01T23:30:00-05:00";
+const COMMITTER_DATE: &str = "2024-03-09T00:30:00+02:00";
+
+#[fixture]
+fn repo() -> TestRepo {
+    let repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.commit_authored_at("0.1.0-pre.1", AUTHOR_DATE, COMMITTER_DATE);
+    repo
+}
+
+#[rstest]
+#[case("CommitterUtc", "2024-03-08", "2024", "03", "08")]
+#[case("CommitterLocal", "2024-03-09", "2024", "03", "09")]
+#[case("Author", "2024-02-01", "2024", "02", "01")]
+fn test_that_commit_date_is_taken_from_configured_source(
+    mut repo: TestRepo,
+    #[case] source: &str,
+    #[case] date: &str,
+    #[case] year: &str,
+    #[case] month: &str,
+    #[case] day: &str,
+) {
+    repo.config.commit_date_source = source.to_string();
+
+    repo.assert()
+        .commit_date(date)
+        .commit_year(year)
+        .commit_month(month)
+        .commit_day(day);
+}
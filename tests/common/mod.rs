@@ -1,4 +1,4 @@
-use git_versioner::config::{Configuration, DefaultConfig};
+use git_versioner::config::{BranchPatterns, Configuration, DefaultConfig};
 use git_versioner::{GitVersion, GitVersioner};
 use rstest::fixture;
 use std::cell::RefCell;
@@ -22,15 +22,54 @@ pub struct TestRepo {
 pub struct TestConfig {
     pub path: PathBuf,
     pub main_branch: String,
-    pub release_branch: String,
-    pub feature_branch: String,
+    pub release_branch: BranchPatterns,
+    pub feature_branch: BranchPatterns,
     pub tag_prefix: String,
     pub pre_release_tag: String,
     pub patch_pre_release_tag: String,
     pub commit_message_incrementing: String,
     pub assembly_informational_format: String,
+    pub commit_date_source: String,
+    pub scheme: String,
+    pub cal_ver_format: String,
+    pub include_remote_branches: bool,
+    pub pre_release_format: String,
+    pub tag_selection: String,
+    pub build_metadata_format: String,
+    pub rebuild_counter_env: String,
+    pub feature_label_prefix: String,
+    pub branch_precedence: String,
+    pub build_metadata_mode: String,
+    pub sem_ver_format: String,
+    pub output_env_file_var: String,
+    pub annotations: bool,
+    pub teamcity_system_parameters: bool,
+    pub teamcity_parameter_prefix: String,
+    pub export_variables: Vec<String>,
+    pub omit_fields: Vec<String>,
+    pub pr_label_bumps: Vec<String>,
+    pub snapshot: bool,
+    pub increment_on_first_commit: bool,
+    pub tag_message_channel: bool,
+    pub pre_release_number_padding: u64,
+    pub preferred_tag_prefix: String,
+    pub feature_branch_mode: String,
+    pub tag_message_template: String,
+    pub bump_scan: String,
+    pub ignore_prerelease_tags: bool,
+    pub apply_replace_refs: bool,
+    pub trunk_pre_release_tag: String,
     pub continuous_delivery: bool,
+    pub loose_tag_versions: bool,
+    pub require_signed_tags: bool,
+    pub case_insensitive_branches: bool,
     pub as_release: bool,
+    pub no_metadata: bool,
+    pub fail_on_dirty: bool,
+    pub strict_semver: bool,
+    pub strict_sources: bool,
+    pub weight_base: Option<u64>,
+    pub branch_config: Vec<String>,
 }
 
 macro_rules! config_getter {
@@ -44,15 +83,54 @@ macro_rules! config_getter {
 impl Configuration for TestConfig {
     config_getter!(path, PathBuf);
     config_getter!(main_branch, str);
-    config_getter!(release_branch, str);
-    config_getter!(feature_branch, str);
+    config_getter!(release_branch, [String]);
+    config_getter!(feature_branch, [String]);
     config_getter!(tag_prefix, str);
     config_getter!(pre_release_tag, str);
     config_getter!(patch_pre_release_tag, str);
     config_getter!(commit_message_incrementing, str);
     config_getter!(assembly_informational_format, str);
+    config_getter!(commit_date_source, str);
+    config_getter!(scheme, str);
+    config_getter!(cal_ver_format, str);
+    config_getter!(include_remote_branches, bool);
+    config_getter!(pre_release_format, str);
+    config_getter!(tag_selection, str);
+    config_getter!(build_metadata_format, str);
+    config_getter!(rebuild_counter_env, str);
+    config_getter!(feature_label_prefix, str);
+    config_getter!(branch_precedence, str);
+    config_getter!(build_metadata_mode, str);
+    config_getter!(sem_ver_format, str);
+    config_getter!(output_env_file_var, str);
+    config_getter!(annotations, bool);
+    config_getter!(teamcity_system_parameters, bool);
+    config_getter!(teamcity_parameter_prefix, str);
+    config_getter!(export_variables, [String]);
+    config_getter!(omit_fields, [String]);
+    config_getter!(pr_label_bumps, [String]);
+    config_getter!(snapshot, bool);
+    config_getter!(increment_on_first_commit, bool);
+    config_getter!(tag_message_channel, bool);
+    config_getter!(pre_release_number_padding, u64);
+    config_getter!(preferred_tag_prefix, str);
+    config_getter!(feature_branch_mode, str);
+    config_getter!(tag_message_template, str);
+    config_getter!(bump_scan, str);
+    config_getter!(ignore_prerelease_tags, bool);
+    config_getter!(apply_replace_refs, bool);
+    config_getter!(trunk_pre_release_tag, str);
     config_getter!(continuous_delivery, bool);
+    config_getter!(loose_tag_versions, bool);
+    config_getter!(require_signed_tags, bool);
+    config_getter!(case_insensitive_branches, bool);
     config_getter!(as_release, bool);
+    config_getter!(no_metadata, bool);
+    config_getter!(fail_on_dirty, bool);
+    config_getter!(strict_semver, bool);
+    config_getter!(strict_sources, bool);
+    config_getter!(weight_base, Option<u64>);
+    config_getter!(branch_config, [String]);
 }
 
 impl Default for TestConfig {
@@ -68,8 +146,47 @@ impl Default for TestConfig {
             patch_pre_release_tag: default.patch_pre_release_tag,
             commit_message_incrementing: default.commit_message_incrementing,
             assembly_informational_format: default.assembly_informational_format,
+            commit_date_source: default.commit_date_source,
+            scheme: default.scheme,
+            cal_ver_format: default.cal_ver_format,
+            include_remote_branches: default.include_remote_branches,
+            pre_release_format: default.pre_release_format,
+            tag_selection: default.tag_selection,
+            build_metadata_format: default.build_metadata_format,
+            rebuild_counter_env: default.rebuild_counter_env,
+            feature_label_prefix: default.feature_label_prefix,
+            branch_precedence: default.branch_precedence,
+            build_metadata_mode: default.build_metadata_mode,
+            sem_ver_format: default.sem_ver_format,
+            output_env_file_var: default.output_env_file_var,
+            annotations: default.annotations,
+            teamcity_system_parameters: default.teamcity_system_parameters,
+            teamcity_parameter_prefix: default.teamcity_parameter_prefix,
+            export_variables: default.export_variables,
+            omit_fields: default.omit_fields,
+            pr_label_bumps: default.pr_label_bumps,
+            snapshot: default.snapshot,
+            increment_on_first_commit: default.increment_on_first_commit,
+            tag_message_channel: default.tag_message_channel,
+            pre_release_number_padding: default.pre_release_number_padding,
+            preferred_tag_prefix: default.preferred_tag_prefix,
+            feature_branch_mode: default.feature_branch_mode,
+            tag_message_template: default.tag_message_template,
+            bump_scan: default.bump_scan,
+            ignore_prerelease_tags: default.ignore_prerelease_tags,
+            apply_replace_refs: default.apply_replace_refs,
+            trunk_pre_release_tag: default.trunk_pre_release_tag,
             continuous_delivery: default.continuous_delivery,
+            loose_tag_versions: false,
+            require_signed_tags: false,
+            case_insensitive_branches: default.case_insensitive_branches,
             as_release: false,
+            no_metadata: false,
+            fail_on_dirty: false,
+            strict_semver: false,
+            strict_sources: false,
+            weight_base: None,
+            branch_config: Vec::new(),
         }
     }
 }
@@ -117,6 +234,23 @@ impl TestRepo {
         self.read_head_sha_and_date()
     }
 
+    pub fn commit_authored_at(
+        &self,
+        message: &str,
+        author_date: &str,
+        committer_date: &str,
+    ) -> (String, String) {
+        self.execute_with_env(
+            &["commit", "--allow-empty", "-m", message],
+            &[
+                ("GIT_AUTHOR_DATE", author_date),
+                ("GIT_COMMITTER_DATE", committer_date),
+            ],
+            &format!("commit {message} authored at {author_date}"),
+        );
+        self.read_head_sha_and_date()
+    }
+
     pub fn branch(&self, name: &str) {
         self.execute(&["branch", name], &format!("branch {name}"));
         self.checkout(name);
@@ -392,17 +526,27 @@ macro_rules! config_assertion {
 #[allow(dead_code)]
 impl Assertable {
     config_assertion!(full_sem_ver, &str);
+    config_assertion!(next_version_tag, &str);
+    config_assertion!(tag_name, &str);
     config_assertion!(branch_name, &str);
     config_assertion!(escaped_branch_name, &str);
+    config_assertion!(pre_release_number, u64);
     config_assertion!(weighted_pre_release_number, u64);
+    config_assertion!(weight_source, &str);
     config_assertion!(assembly_sem_ver, &str);
     config_assertion!(assembly_sem_file_ver, &str);
     config_assertion!(sha, &str);
     config_assertion!(short_sha, &str);
+    config_assertion!(sem_ver, &str);
+    config_assertion!(build_metadata, &str);
+    config_assertion!(full_build_meta_data, &str);
     config_assertion!(version_source_sha, &str);
+    config_assertion!(version_source_kind, &str);
     config_assertion!(major_minor_patch_version_source_sha, &str);
+    config_assertion!(pre_release_label, &str);
     config_assertion!(pre_release_label_with_dash, &str);
     config_assertion!(informational_version, &str);
+    config_assertion!(commit_date, &str);
     config_assertion!(commit_year, &str);
     config_assertion!(commit_month, &str);
     config_assertion!(commit_day, &str);
@@ -410,6 +554,11 @@ impl Assertable {
     config_assertion!(cal_ver_month, &str);
     config_assertion!(cal_ver_day, &str);
     config_assertion!(cal_ver_minor, u64);
+    config_assertion!(commits_since_version_source, u64);
+    config_assertion!(version_source_distance, u64);
+    config_assertion!(commits_since_release_branch, i64);
+    config_assertion!(describe, &str);
+    config_assertion!(channel, &str);
 
     pub fn previous_pre_releases(self, expected: &[&str]) -> Self {
         let actual = &self.result.previous_pre_releases;
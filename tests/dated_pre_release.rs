@@ -0,0 +1,63 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo(#[default(MAIN_BRANCH)] main_branch: &str) -> TestRepo {
+    let mut repo = TestRepo::initialize(main_branch);
+    repo.config.commit_message_incrementing = "Disabled".to_string();
+    repo.config.pre_release_format = "Dated".to_string();
+    repo.commit_at("0.1.0-pre.20240516.1", "2024-05-16T10:00:00Z");
+    repo.tag("v1.2.0");
+    repo
+}
+
+#[rstest]
+#[should_panic(
+    expected = r#"Invalid value "foo" for PreReleaseFormat. Should be "Counted" or "Dated"."#
+)]
+fn test_providing_unknown_pre_release_format_panics(mut repo: TestRepo) {
+    repo.config.pre_release_format = "foo".to_string();
+    repo.assert(); // panics
+}
+
+#[rstest]
+fn test_pre_release_contains_head_commit_date_and_commit_count(repo: TestRepo) {
+    repo.commit_at("1.3.0-pre.20240517.1", "2024-05-17T09:00:00Z");
+    repo.commit_at("1.3.0-pre.20240517.2", "2024-05-17T15:00:00Z");
+
+    repo.assert()
+        .full_sem_ver("1.3.0-pre.20240517.2")
+        .pre_release_number(2);
+}
+
+#[rstest]
+fn test_pre_release_only_counts_commits_made_on_the_day_of_head(repo: TestRepo) {
+    repo.commit_at("1.3.0-pre.20240516.1", "2024-05-16T15:00:00Z");
+    repo.commit_at("1.3.0-pre.20240517.1", "2024-05-17T09:00:00Z");
+
+    repo.assert().full_sem_ver("1.3.0-pre.20240517.1");
+}
+
+#[rstest]
+fn test_pre_release_date_is_in_utc(repo: TestRepo) {
+    repo.commit_at("1.3.0-pre.20240517.1", "2024-05-18T01:00:00+02:00");
+
+    repo.assert().full_sem_ver("1.3.0-pre.20240517.1");
+}
+
+#[rstest]
+fn test_continuous_delivery_continues_pre_release_tags_of_the_same_day(mut repo: TestRepo) {
+    repo.config.continuous_delivery = true;
+    repo.commit_at("1.3.0-pre.20240517.1", "2024-05-17T09:00:00Z");
+    repo.tag("v1.3.0-pre.20240517.1");
+    repo.commit_at("1.3.0-pre.20240517.2", "2024-05-17T15:00:00Z");
+
+    repo.assert().full_sem_ver("1.3.0-pre.20240517.2");
+
+    repo.tag("v1.3.0-pre.20240517.2");
+    repo.commit_at("1.3.0-pre.20240518.1", "2024-05-18T09:00:00Z");
+
+    repo.assert().full_sem_ver("1.3.0-pre.20240518.1");
+}
@@ -0,0 +1,69 @@
+use git_versioner::config::{
+    ASSEMBLY_INFORMATIONAL_FORMAT, COMMIT_MESSAGE_INCREMENTING, Configuration, ConfigurationFile,
+    DefaultConfig, FEATURE_BRANCH, MAIN_BRANCH, PATCH_PRE_RELEASE_TAG, RELEASE_BRANCH, TAG_PREFIX,
+};
+use rstest::rstest;
+use std::path::PathBuf;
+
+#[rstest]
+fn test_that_overrides_replace_defaults_and_missing_values_preserve_them() {
+    let overrides = ConfigurationFile {
+        main_branch: Some("^stem$".to_string()),
+        pre_release_tag: Some("alpha".to_string()),
+        ..Default::default()
+    };
+
+    let config = DefaultConfig::default().with_overrides(overrides);
+
+    assert_eq!(config.main_branch(), "^stem$");
+    assert_eq!(config.pre_release_tag(), "alpha");
+    assert_eq!(config.release_branch(), [RELEASE_BRANCH]);
+    assert_eq!(config.feature_branch(), [FEATURE_BRANCH]);
+    assert_eq!(config.tag_prefix(), TAG_PREFIX);
+    assert_eq!(config.patch_pre_release_tag(), PATCH_PRE_RELEASE_TAG);
+    assert_eq!(
+        config.commit_message_incrementing(),
+        COMMIT_MESSAGE_INCREMENTING
+    );
+    assert_eq!(
+        config.assembly_informational_format(),
+        ASSEMBLY_INFORMATIONAL_FORMAT
+    );
+}
+
+#[rstest]
+fn test_that_builder_applies_values_on_top_of_defaults() {
+    let config = DefaultConfig::builder()
+        .path("some/repository")
+        .tag_prefix("my/v")
+        .continuous_delivery(true)
+        .overrides(ConfigurationFile {
+            commit_message_incrementing: Some("Enabled".to_string()),
+            ..Default::default()
+        })
+        .build();
+
+    assert_eq!(config.path(), &PathBuf::from("some/repository"));
+    assert_eq!(config.tag_prefix(), "my/v");
+    assert!(*config.continuous_delivery());
+    assert_eq!(config.commit_message_incrementing(), "Enabled");
+    assert_eq!(config.main_branch(), MAIN_BRANCH);
+}
+
+#[rstest]
+#[case::single(r#"ReleaseBranch = "^rel/(?<BranchName>.+)$""#, &["^rel/(?<BranchName>.+)$"])]
+#[case::multiple(
+    r#"ReleaseBranch = ["^rel/(?<BranchName>.+)$", "^release/(?<BranchName>.+)$"]"#,
+    &["^rel/(?<BranchName>.+)$", "^release/(?<BranchName>.+)$"]
+)]
+fn test_that_branch_patterns_accept_single_pattern_or_list(
+    #[case] content: &str,
+    #[case] expected: &[&str],
+) {
+    let overrides: ConfigurationFile = toml::from_str(content).unwrap();
+
+    let config = DefaultConfig::default().with_overrides(overrides);
+
+    assert_eq!(config.release_branch(), expected);
+    assert_eq!(config.feature_branch(), [FEATURE_BRANCH]);
+}
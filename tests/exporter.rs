@@ -0,0 +1,110 @@
+use git_versioner::GitVersion;
+use git_versioner::exporter::{
+    Exporter, GitHubExporter, GitLabExporter, TeamCityExporter, variables_of,
+};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn version() -> GitVersion {
+    GitVersion {
+        full_sem_ver: "1.2.0-pre.3".to_string(),
+        branch_name: "main".to_string(),
+        ..GitVersion::default()
+    }
+}
+
+fn variables(version: &GitVersion) -> Vec<(String, String)> {
+    variables_of(
+        version,
+        &["FullSemVer".to_string(), "BranchName".to_string()],
+    )
+    .unwrap()
+}
+
+#[rstest]
+fn test_github_exporter_renders_prefixed_and_camel_case_outputs(version: GitVersion) {
+    let rendered = GitHubExporter { annotations: true }.render(&version, &variables(&version));
+
+    assert_eq!(
+        rendered,
+        "GitVersion_BranchName=main\nbranchName=main\nGitVersion_FullSemVer=1.2.0-pre.3\nfullSemVer=1.2.0-pre.3\n"
+    );
+}
+
+#[rstest]
+fn test_gitlab_exporter_renders_prefixed_variables(version: GitVersion) {
+    let rendered = GitLabExporter.render(&version, &variables(&version));
+
+    assert_eq!(
+        rendered,
+        "GitVersion_BranchName=main\nGitVersion_FullSemVer=1.2.0-pre.3\n"
+    );
+}
+
+#[rstest]
+#[case::with_system_parameters(
+    true,
+    "##teamcity[setParameter name='GitVersion.BranchName' value='main']\n\
+     ##teamcity[setParameter name='system.GitVersion.BranchName' value='main']\n\
+     ##teamcity[setParameter name='GitVersion.FullSemVer' value='1.2.0-pre.3']\n\
+     ##teamcity[setParameter name='system.GitVersion.FullSemVer' value='1.2.0-pre.3']\n"
+)]
+#[case::without_system_parameters(
+    false,
+    "##teamcity[setParameter name='GitVersion.BranchName' value='main']\n\
+     ##teamcity[setParameter name='GitVersion.FullSemVer' value='1.2.0-pre.3']\n"
+)]
+fn test_teamcity_exporter_renders_service_messages(
+    version: GitVersion,
+    #[case] system_parameters: bool,
+    #[case] expected: &str,
+) {
+    let exporter = TeamCityExporter {
+        parameter_prefix: "GitVersion",
+        system_parameters,
+    };
+
+    assert_eq!(exporter.render(&version, &variables(&version)), expected);
+}
+
+#[rstest]
+fn test_repository_paths_are_not_exported_by_default(mut version: GitVersion) {
+    version.repository_path = "/repositories/project".to_string();
+    version.git_dir = "/repositories/project/.git".to_string();
+
+    let keys = variables_of(&version, &[])
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect::<Vec<_>>();
+
+    assert!(keys.contains(&"FullSemVer".to_string()));
+    assert!(!keys.contains(&"RepositoryPath".to_string()));
+    assert!(!keys.contains(&"GitDir".to_string()));
+}
+
+#[rstest]
+fn test_repository_paths_are_exported_if_configured(mut version: GitVersion) {
+    version.repository_path = "/repositories/project".to_string();
+    version.git_dir = "/repositories/project/.git".to_string();
+
+    let variables = variables_of(
+        &version,
+        &["RepositoryPath".to_string(), "GitDir".to_string()],
+    )
+    .unwrap();
+
+    assert_eq!(
+        variables,
+        vec![
+            (
+                "GitDir".to_string(),
+                "/repositories/project/.git".to_string()
+            ),
+            (
+                "RepositoryPath".to_string(),
+                "/repositories/project".to_string()
+            ),
+        ]
+    );
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo() -> TestRepo {
+    let repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.commit("0.1.0-pre.1");
+    repo.tag("v1.0.0");
+    repo
+}
+
+#[rstest]
+#[should_panic(
+    expected = r#"Invalid value "Label" for FeatureBranchMode. Should be "PreRelease" or "Metadata"."#
+)]
+fn test_providing_unknown_feature_branch_mode_panics(mut repo: TestRepo) {
+    repo.config.feature_branch_mode = "Label".to_string();
+    repo.assert(); // panics
+}
+
+#[rstest]
+#[case::pre_release("PreRelease", "1.1.0-feature-x.2", 30002, "Feature")]
+#[case::metadata("Metadata", "1.1.0-pre.3+feature-x.2", 55003, "Main")]
+fn test_feature_branch_from_main_branch(
+    mut repo: TestRepo,
+    #[case] mode: &str,
+    #[case] expected: &str,
+    #[case] weighted_pre_release_number: u64,
+    #[case] weight_source: &str,
+) {
+    repo.config.feature_branch_mode = mode.to_string();
+    repo.commit("1.1.0-pre.1");
+    repo.commit("1.1.0-pre.2");
+    repo.commit_and_assert("1.1.0-pre.3");
+    repo.branch("feature/feature_x");
+    repo.commit("feature commit 1");
+    repo.commit("feature commit 2");
+
+    repo.assert()
+        .full_sem_ver(expected)
+        .weighted_pre_release_number(weighted_pre_release_number)
+        .weight_source(weight_source)
+        .branch_name("feature/feature_x");
+}
+
+#[rstest]
+#[case::pre_release("PreRelease", "1.0.1-fix.2", 30002, "Feature")]
+#[case::metadata("Metadata", "1.0.1-pre.1+fix.2", 55001, "Release")]
+fn test_feature_branch_from_release_branch(
+    mut repo: TestRepo,
+    #[case] mode: &str,
+    #[case] expected: &str,
+    #[case] weighted_pre_release_number: u64,
+    #[case] weight_source: &str,
+) {
+    repo.config.feature_branch_mode = mode.to_string();
+    repo.branch("release/1.0.0");
+    repo.commit_and_assert("1.0.1-pre.1");
+    repo.branch("feature/fix");
+    repo.commit("feature commit 1");
+    repo.commit("feature commit 2");
+
+    repo.assert()
+        .full_sem_ver(expected)
+        .weighted_pre_release_number(weighted_pre_release_number)
+        .weight_source(weight_source);
+}
+
+#[rstest]
+#[case::pre_release("PreRelease", "1.1.0-fix.1")]
+#[case::metadata("Metadata", "1.1.0-pre.0+fix.1")]
+fn test_feature_branch_from_release_tag(
+    mut repo: TestRepo,
+    #[case] mode: &str,
+    #[case] expected: &str,
+) {
+    repo.config.feature_branch_mode = mode.to_string();
+    repo.branch("feature/fix");
+    repo.commit_and_assert(expected);
+}
+
+#[rstest]
+fn test_metadata_mode_does_not_affect_base_branches(mut repo: TestRepo) {
+    repo.config.feature_branch_mode = "Metadata".to_string();
+    repo.commit_and_assert("1.1.0-pre.1");
+    repo.branch("release/1.1.0");
+    repo.commit_and_assert("1.1.0-pre.2");
+    repo.branch("feature/x");
+    repo.assert().full_sem_ver("1.1.0-pre.2");
+}
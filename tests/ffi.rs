@@ -0,0 +1,112 @@
+#![cfg(feature = "ffi")]
+
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use git_versioner::GitVersion;
+use git_versioner::ffi::{git_versioner_calculate, git_versioner_free, git_versioner_last_error};
+use rstest::{fixture, rstest};
+use std::ffi::{CStr, CString, c_char};
+use std::ptr;
+
+#[fixture]
+fn repo() -> TestRepo {
+    let repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.commit("0.1.0-pre.1");
+    repo
+}
+
+fn calculate(path: *const c_char, config_json: *const c_char) -> Result<GitVersion, String> {
+    let result = unsafe { git_versioner_calculate(path, config_json) };
+    if result.is_null() {
+        let error = unsafe { CStr::from_ptr(git_versioner_last_error()) };
+        return Err(error.to_string_lossy().into_owned());
+    }
+    assert!(git_versioner_last_error().is_null());
+
+    let json = unsafe { CStr::from_ptr(result) }
+        .to_str()
+        .unwrap()
+        .to_owned();
+    unsafe { git_versioner_free(result) };
+    Ok(serde_json::from_str(&json).unwrap())
+}
+
+fn path_of(repo: &TestRepo) -> CString {
+    CString::new(repo.config.path.to_str().unwrap()).unwrap()
+}
+
+#[rstest]
+fn test_that_version_is_calculated_without_configuration(repo: TestRepo) {
+    let path = path_of(&repo);
+
+    let version = calculate(path.as_ptr(), ptr::null()).unwrap();
+
+    assert_eq!(version.full_sem_ver, "0.1.0-pre.1");
+}
+
+#[rstest]
+fn test_that_configuration_json_is_applied(repo: TestRepo) {
+    let path = path_of(&repo);
+    let config = CString::new(r#"{"PreReleaseTag": "alpha"}"#).unwrap();
+
+    let version = calculate(path.as_ptr(), config.as_ptr()).unwrap();
+
+    assert_eq!(version.full_sem_ver, "0.1.0-alpha.1");
+}
+
+#[rstest]
+fn test_that_null_path_is_reported_as_error() {
+    let error = calculate(ptr::null(), ptr::null()).unwrap_err();
+
+    assert_eq!(error, "Path must not be null");
+}
+
+#[rstest]
+fn test_that_invalid_utf8_is_reported_as_error(repo: TestRepo) {
+    let path = path_of(&repo);
+    let config = CString::new(vec![b'{', 0xff, b'}']).unwrap();
+
+    let error = calculate(path.as_ptr(), config.as_ptr()).unwrap_err();
+
+    assert!(
+        error.starts_with("Configuration is not valid UTF-8"),
+        "{error}"
+    );
+}
+
+#[rstest]
+fn test_that_invalid_configuration_json_is_reported_as_error(repo: TestRepo) {
+    let path = path_of(&repo);
+    let config = CString::new("not json").unwrap();
+
+    assert!(calculate(path.as_ptr(), config.as_ptr()).is_err());
+}
+
+#[rstest]
+fn test_that_panics_are_reported_as_error_instead_of_unwinding(repo: TestRepo) {
+    let path = path_of(&repo);
+    let config = CString::new(r#"{"Scheme": "foo"}"#).unwrap();
+
+    let error = calculate(path.as_ptr(), config.as_ptr()).unwrap_err();
+
+    assert_eq!(
+        error,
+        r#"Invalid value "foo" for Scheme. Should be "SemVer" or "CalVer"."#
+    );
+}
+
+#[rstest]
+fn test_that_last_error_is_cleared_by_successful_call(repo: TestRepo) {
+    let path = path_of(&repo);
+    calculate(ptr::null(), ptr::null()).unwrap_err();
+
+    calculate(path.as_ptr(), ptr::null()).unwrap();
+
+    assert!(git_versioner_last_error().is_null());
+}
+
+#[rstest]
+fn test_that_freeing_null_is_a_no_op() {
+    unsafe { git_versioner_free(ptr::null_mut()) };
+}
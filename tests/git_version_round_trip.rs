@@ -0,0 +1,39 @@
+use git_versioner::GitVersion;
+use rstest::rstest;
+
+#[rstest]
+fn test_that_default_version_survives_json_round_trip() {
+    let version = GitVersion::default();
+
+    let json = serde_json::to_string(&version).unwrap();
+
+    assert_eq!(serde_json::from_str::<GitVersion>(&json).unwrap(), version);
+}
+
+#[rstest]
+fn test_that_constructed_version_survives_json_round_trip() {
+    let version = GitVersion {
+        major: 1,
+        minor: 2,
+        patch: 3,
+        previous_pre_releases: vec!["1.2.3-pre.1".to_string()],
+        pre_release_tag: "pre.2".to_string(),
+        pre_release_tag_with_dash: "-pre.2".to_string(),
+        pre_release_label: "pre".to_string(),
+        pre_release_label_with_dash: "-pre".to_string(),
+        pre_release_number: 2,
+        weighted_pre_release_number: 55002,
+        weight_source: "Main".to_string(),
+        major_minor_patch: "1.2.3".to_string(),
+        sem_ver: "1.2.3-pre.2".to_string(),
+        full_sem_ver: "1.2.3-pre.2".to_string(),
+        branch_name: "main".to_string(),
+        commits_since_version_source: 2,
+        commits_since_release_branch: -1,
+        ..GitVersion::default()
+    };
+
+    let json = serde_json::to_string(&version).unwrap();
+
+    assert_eq!(serde_json::from_str::<GitVersion>(&json).unwrap(), version);
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo() -> TestRepo {
+    let repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.commit("0.1.0-pre.1");
+    repo
+}
+
+#[rstest]
+fn test_continuous_delivery_continues_from_prerelease_tags_by_default(mut repo: TestRepo) {
+    repo.config.continuous_delivery = true;
+    repo.tag("v1.0.0");
+    repo.commit("1.1.0-pre.1");
+    let (sha, _) = repo.tag("v1.1.0-pre.7");
+    repo.commit("1.1.0-pre.8");
+
+    repo.assert()
+        .full_sem_ver("1.1.0-pre.8")
+        .version_source_sha(&sha);
+}
+
+#[rstest]
+fn test_continuous_delivery_ignores_prerelease_tags_if_configured(mut repo: TestRepo) {
+    repo.config.continuous_delivery = true;
+    repo.config.ignore_prerelease_tags = true;
+    let (sha, _) = repo.tag("v1.0.0");
+    repo.commit("1.1.0-pre.1");
+    repo.tag("v1.1.0-pre.7");
+    repo.commit("1.1.0-pre.8");
+
+    repo.assert()
+        .full_sem_ver("1.1.0-pre.1")
+        .version_source_sha(&sha);
+}
+
+#[rstest]
+fn test_ignored_prerelease_tags_leave_commit_height_numbering_untouched(mut repo: TestRepo) {
+    repo.config.ignore_prerelease_tags = true;
+    let (sha, _) = repo.tag("v1.0.0");
+    repo.commit("1.1.0-pre.1");
+    repo.tag("v1.1.0-pre.7");
+    repo.commit("1.1.0-pre.2");
+    repo.tag("v1.1.0-rc.1");
+
+    repo.commit_and_assert("1.1.0-pre.3")
+        .version_source_sha(&sha);
+}
+
+#[rstest]
+#[case::by_default(false, &["v1.0.0-pre.1", "v1.0.0-rc.1"])]
+#[case::ignored(true, &[])]
+fn test_previous_pre_releases_of_release(
+    mut repo: TestRepo,
+    #[case] ignore_prerelease_tags: bool,
+    #[case] expected: &[&str],
+) {
+    repo.config.ignore_prerelease_tags = ignore_prerelease_tags;
+    repo.tag("v1.0.0-pre.1");
+    repo.commit("1.0.0-pre.2");
+    repo.tag("v1.0.0-rc.1");
+    repo.tag("v1.0.0");
+
+    repo.assert().previous_pre_releases(expected);
+}
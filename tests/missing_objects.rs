@@ -0,0 +1,60 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use git_versioner::GitVersioner;
+use rstest::{fixture, rstest};
+use std::fs;
+use std::path::Path;
+
+#[fixture]
+fn repo() -> TestRepo {
+    let mut repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.config.commit_message_incrementing = "Disabled".to_string();
+    repo
+}
+
+fn remove_object(repo: &TestRepo, sha: &str) {
+    let object = Path::new(repo.path())
+        .join(".git/objects")
+        .join(&sha[..2])
+        .join(&sha[2..]);
+    fs::remove_file(object).unwrap();
+}
+
+fn tag_stale_commit(repo: &TestRepo, tag: &str) -> String {
+    repo.branch("stale");
+    let (sha, _) = repo.commit("stale");
+    repo.tag_annotated(tag);
+    repo.checkout(MAIN_BRANCH);
+    repo.execute(&["branch", "-D", "stale"], "delete branch stale");
+    sha
+}
+
+#[rstest]
+fn test_tag_referencing_missing_commit_is_skipped(repo: TestRepo) {
+    repo.commit("0.1.0-pre.1");
+    let (sha, _) = repo.tag("v1.0.0");
+    let stale = tag_stale_commit(&repo, "v2.0.0");
+    repo.commit("1.1.0-pre.1");
+
+    remove_object(&repo, &stale);
+
+    // The graph for assertion context cannot be drawn with a missing object
+    let version = GitVersioner::calculate_version(&repo.config).unwrap();
+
+    assert_eq!(version.full_sem_ver, "1.1.0-pre.1");
+    assert_eq!(version.version_source_sha, sha);
+}
+
+#[rstest]
+fn test_calculation_fails_when_only_tags_referencing_missing_commits_exist(repo: TestRepo) {
+    repo.commit("0.1.0-pre.1");
+    let stale = tag_stale_commit(&repo, "v2.0.0");
+    repo.commit("0.1.0-pre.2");
+
+    remove_object(&repo, &stale);
+
+    let error = GitVersioner::calculate_version(&repo.config).unwrap_err();
+
+    assert!(error.to_string().contains("2.0.0"), "{error}");
+}
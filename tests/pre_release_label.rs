@@ -0,0 +1,33 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo(#[default(MAIN_BRANCH)] main_branch: &str) -> TestRepo {
+    let repo = TestRepo::initialize(main_branch);
+    repo.commit("0.1.0-pre.1");
+    repo.tag("v1.0.0");
+    repo
+}
+
+#[rstest]
+fn test_that_pre_release_label_keeps_dots_of_pre_release_tag(mut repo: TestRepo) {
+    repo.config.pre_release_tag = "alpha.beta".to_string();
+    repo.commit("1.1.0-alpha.beta.1");
+    repo.commit("1.1.0-alpha.beta.2");
+
+    repo.assert()
+        .full_sem_ver("1.1.0-alpha.beta.2")
+        .pre_release_label("alpha.beta")
+        .pre_release_label_with_dash("-alpha.beta")
+        .pre_release_number(2);
+}
+
+#[rstest]
+fn test_that_pre_release_label_excludes_date_of_dated_pre_release(mut repo: TestRepo) {
+    repo.config.pre_release_format = "Dated".to_string();
+    repo.commit("1.1.0-pre.<date>.1");
+
+    repo.assert().pre_release_label("pre").pre_release_number(1);
+}
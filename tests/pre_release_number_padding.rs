@@ -0,0 +1,65 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo(#[default(MAIN_BRANCH)] main_branch: &str) -> TestRepo {
+    let mut repo = TestRepo::initialize(main_branch);
+    repo.config.pre_release_number_padding = 4;
+    repo.commit("0.1.0-pre0001");
+    repo.tag("v1.0.0");
+    repo
+}
+
+#[rstest]
+fn test_pre_release_number_is_not_padded_by_default(mut repo: TestRepo) {
+    repo.config.pre_release_number_padding = 0;
+
+    repo.commit_and_assert("1.1.0-pre.1");
+}
+
+#[rstest]
+fn test_padded_pre_release_number_is_joined_to_label(repo: TestRepo) {
+    repo.commit_and_assert("1.1.0-pre0001");
+    repo.commit_and_assert("1.1.0-pre0002")
+        .pre_release_label_with_dash("-pre")
+        .pre_release_number(2)
+        .weighted_pre_release_number(55002);
+}
+
+#[rstest]
+fn test_padded_pre_release_numbers_keep_their_width_beyond_nine(repo: TestRepo) {
+    for _ in 0..9 {
+        repo.commit("1.1.0-pre000x");
+    }
+    repo.assert().full_sem_ver("1.1.0-pre0009");
+
+    repo.commit_and_assert("1.1.0-pre0010")
+        .pre_release_number(10);
+}
+
+#[rstest]
+fn test_continuous_delivery_continues_padded_pre_release_tags(mut repo: TestRepo) {
+    repo.config.continuous_delivery = true;
+    repo.commit_and_assert("1.1.0-pre0001");
+    repo.tag("v1.1.0-pre0001");
+    repo.commit_and_assert("1.1.0-pre0002");
+}
+
+#[rstest]
+fn test_continuous_delivery_continues_unpadded_pre_release_tags(mut repo: TestRepo) {
+    repo.config.continuous_delivery = true;
+    repo.commit("1.1.0-pre.3");
+    repo.tag("v1.1.0-pre.3");
+    repo.commit_and_assert("1.1.0-pre0004");
+}
+
+#[rstest]
+#[should_panic(
+    expected = r#"Invalid value "4" for PreReleaseNumberPadding. Should be 0 with PreReleaseFormat "Dated"."#
+)]
+fn test_padding_dated_pre_release_numbers_panics(mut repo: TestRepo) {
+    repo.config.pre_release_format = "Dated".to_string();
+    repo.assert(); // panics
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use git_versioner::{GitVersioner, GitVersionerConfig};
+use regex::Regex;
+use rstest::{fixture, rstest};
+
+const BRANCH_COUNT: usize = 25;
+
+#[fixture]
+fn repo() -> TestRepo {
+    let repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.commit("0.1.0-pre.1");
+    repo.tag("v1.0.0");
+    for index in 0..BRANCH_COUNT {
+        repo.checkout(MAIN_BRANCH);
+        repo.branch(&format!("feature/feature-{index}"));
+        repo.commit(&format!("1.1.0-feature-{index}.1"));
+    }
+    repo
+}
+
+#[rstest]
+fn test_that_compiled_configuration_is_reusable_across_many_branches(repo: TestRepo) {
+    let config = GitVersionerConfig::new(&repo.config).unwrap();
+
+    for index in 0..BRANCH_COUNT {
+        repo.checkout(&format!("feature/feature-{index}"));
+
+        let version = GitVersioner::calculate_version_with(&config).unwrap();
+
+        assert_eq!(version.full_sem_ver, format!("1.1.0-feature-{index}.1"));
+    }
+}
+
+#[rstest]
+fn test_that_precompiled_patterns_produce_same_result_as_configuration(repo: TestRepo) {
+    let config = GitVersionerConfig::with_patterns(
+        &repo.config,
+        Regex::new(&repo.config.main_branch).unwrap(),
+        compiled(&repo.config.release_branch),
+        compiled(&repo.config.feature_branch),
+        GitVersionerConfig::version_pattern_for(&repo.config.tag_prefix).unwrap(),
+    );
+
+    let expected = GitVersioner::calculate_version(&repo.config).unwrap();
+    let actual = GitVersioner::calculate_version_with(&config).unwrap();
+
+    assert_eq!(actual, expected);
+}
+
+fn compiled(patterns: &[String]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .map(|pattern| Regex::new(pattern).unwrap())
+        .collect()
+}
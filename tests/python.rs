@@ -0,0 +1,112 @@
+#![cfg(feature = "python")]
+
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use git_versioner::python::{ConfigurationError, RepositoryError, calculate};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo() -> TestRepo {
+    let repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.commit("0.1.0-pre.1");
+    repo
+}
+
+fn path_of(repo: &TestRepo) -> &str {
+    repo.config.path.to_str().unwrap()
+}
+
+fn with_python<T>(f: impl FnOnce(Python<'_>) -> T) -> T {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(f)
+}
+
+fn full_sem_ver(version: &Bound<'_, PyDict>) -> String {
+    version
+        .get_item("FullSemVer")
+        .unwrap()
+        .unwrap()
+        .extract()
+        .unwrap()
+}
+
+#[rstest]
+fn test_that_version_is_calculated_without_configuration(repo: TestRepo) {
+    with_python(|py| {
+        let version = calculate(py, path_of(&repo), None).unwrap();
+
+        assert_eq!(full_sem_ver(&version), "0.1.0-pre.1");
+        let major: u64 = version
+            .get_item("Major")
+            .unwrap()
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(major, 0);
+    });
+}
+
+#[rstest]
+fn test_that_keyword_arguments_are_applied_as_configuration(repo: TestRepo) {
+    with_python(|py| {
+        let config = PyDict::new(py);
+        config.set_item("pre_release_tag", "alpha").unwrap();
+        config
+            .set_item(
+                "release_branch",
+                PyList::new(py, ["^rel/(?<BranchName>.+)$"]).unwrap(),
+            )
+            .unwrap();
+        config.set_item("include_remote_branches", false).unwrap();
+
+        let version = calculate(py, path_of(&repo), Some(&config)).unwrap();
+
+        assert_eq!(full_sem_ver(&version), "0.1.0-alpha.1");
+    });
+}
+
+#[rstest]
+fn test_that_unknown_keyword_argument_raises_configuration_error(repo: TestRepo) {
+    with_python(|py| {
+        let config = PyDict::new(py);
+        config.set_item("unknown_option", "value").unwrap();
+
+        let error = calculate(py, path_of(&repo), Some(&config)).unwrap_err();
+
+        assert!(error.is_instance_of::<ConfigurationError>(py));
+        assert_eq!(
+            error.value(py).to_string(),
+            "Unknown configuration option unknown_option"
+        );
+    });
+}
+
+#[rstest]
+fn test_that_invalid_configuration_value_raises_configuration_error(repo: TestRepo) {
+    with_python(|py| {
+        let config = PyDict::new(py);
+        config.set_item("scheme", "foo").unwrap();
+
+        let error = calculate(py, path_of(&repo), Some(&config)).unwrap_err();
+
+        assert!(error.is_instance_of::<ConfigurationError>(py));
+        assert_eq!(
+            error.value(py).to_string(),
+            r#"Invalid value "foo" for Scheme. Should be "SemVer" or "CalVer"."#
+        );
+    });
+}
+
+#[rstest]
+fn test_that_missing_repository_raises_repository_error() {
+    let directory = tempfile::tempdir().unwrap();
+
+    with_python(|py| {
+        let error = calculate(py, directory.path().to_str().unwrap(), None).unwrap_err();
+
+        assert!(error.is_instance_of::<RepositoryError>(py));
+    });
+}
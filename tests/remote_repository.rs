@@ -45,3 +45,38 @@ fn test_main_branch_considers_remote_release_branches_as_base_version(repo: Test
     clone.checkout(MAIN_BRANCH);
     clone.assert().full_sem_ver("1.1.0-pre.1");
 }
+
+#[rstest]
+fn test_remote_head_is_not_considered_as_source_branch(repo: TestRepo) {
+    repo.commit("0.1.0+1");
+    repo.branch("release/1.0.0");
+    repo.commit("1.0.0+1");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit("1.1.0+1");
+    repo.commit("1.1.0+2");
+
+    let clone = TestRepo::clone(&repo);
+    clone.execute(
+        &["remote", "set-head", "origin", MAIN_BRANCH],
+        "set origin/HEAD",
+    );
+    clone.checkout(MAIN_BRANCH);
+    clone
+        .assert()
+        .full_sem_ver("1.1.0-pre.2")
+        .branch_name(MAIN_BRANCH);
+}
+
+#[rstest]
+fn test_main_branch_ignores_remote_release_branches_when_disabled(repo: TestRepo) {
+    repo.commit("0.1.0+1");
+    repo.branch("release/1.0.0");
+    repo.commit("1.0.0+1");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit("1.1.0+1");
+
+    let mut clone = TestRepo::clone(&repo);
+    clone.config.include_remote_branches = false;
+    clone.checkout(MAIN_BRANCH);
+    clone.assert().full_sem_ver("0.1.0-pre.2");
+}
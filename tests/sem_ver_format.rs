@@ -0,0 +1,53 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo() -> TestRepo {
+    let repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.commit("0.1.0-pre.1");
+    repo
+}
+
+#[rstest]
+fn test_sem_ver_format_without_pre_release_drops_it_from_sem_ver(mut repo: TestRepo) {
+    repo.config.sem_ver_format = "{Major}.{Minor}.{Patch}".to_string();
+
+    repo.assert()
+        .full_sem_ver("0.1.0")
+        .sem_ver("0.1.0")
+        .pre_release_label_with_dash("-pre");
+}
+
+#[rstest]
+fn test_sem_ver_format_can_extend_calculated_sem_ver(mut repo: TestRepo) {
+    repo.config.sem_ver_format =
+        "v{SemVer}{env:GIT_VERSIONER_TEST_UNSET_SUFFIX ?? -local}".to_string();
+
+    repo.assert()
+        .full_sem_ver("v0.1.0-pre.1-local")
+        .sem_ver("v0.1.0-pre.1-local");
+}
+
+#[rstest]
+fn test_sem_ver_format_is_used_by_informational_version(mut repo: TestRepo) {
+    repo.config.sem_ver_format = "{Major}.{Minor}.{Patch}".to_string();
+    repo.config.assembly_informational_format = "{FullSemVer}+{ShortSha}".to_string();
+
+    let version = repo.assert().result;
+
+    assert_eq!(
+        version.informational_version,
+        format!("0.1.0+{}", version.short_sha)
+    );
+}
+
+#[rstest]
+#[should_panic(
+    expected = r#"Invalid value "{Major}.{Minor}.{Unknown}" for SemVerFormat. Should only reference output variables like "{Major}", but "Unknown" is unknown."#
+)]
+fn test_sem_ver_format_with_unknown_variable_panics(mut repo: TestRepo) {
+    repo.config.sem_ver_format = "{Major}.{Minor}.{Unknown}".to_string();
+    repo.assert(); // panics
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use git_versioner::GitVersioner;
+use rstest::{fixture, rstest};
+
+const STUBBED_SIGNATURE: &str = "-----BEGIN PGP SIGNATURE-----
+stubbed
+-----END PGP SIGNATURE-----";
+
+#[fixture]
+fn repo() -> TestRepo {
+    let mut repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.config.require_signed_tags = true;
+    repo.commit("0.1.0-pre.1");
+    repo
+}
+
+#[rstest]
+fn test_that_lightweight_release_tag_as_version_source_fails(repo: TestRepo) {
+    repo.tag("v1.0.0");
+    repo.commit("1.1.0-pre.1");
+
+    let error = GitVersioner::calculate_version(&repo.config).unwrap_err();
+
+    assert!(error.to_string().contains("v1.0.0"), "{error}");
+}
+
+#[rstest]
+fn test_that_unsigned_annotated_release_tag_as_version_source_fails(repo: TestRepo) {
+    repo.tag_annotated("v1.0.0");
+
+    assert!(GitVersioner::calculate_version(&repo.config).is_err());
+}
+
+#[rstest]
+fn test_that_signed_annotated_release_tag_as_version_source_passes(repo: TestRepo) {
+    repo.execute(
+        &[
+            "tag",
+            "-a",
+            "v1.0.0",
+            "-m",
+            &format!("v1.0.0\n\n{STUBBED_SIGNATURE}"),
+        ],
+        "create signed tag v1.0.0",
+    );
+    repo.commit("1.1.0-pre.1");
+
+    repo.assert().full_sem_ver("1.1.0-pre.1");
+}
+
+#[rstest]
+fn test_that_pre_release_without_release_tag_source_passes(repo: TestRepo) {
+    repo.tag("v0.1.0-pre.1");
+    repo.commit("0.1.0-pre.2");
+
+    repo.assert().full_sem_ver("0.1.0-pre.2");
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo() -> TestRepo {
+    let mut repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.config.tag_message_channel = true;
+    repo.commit("0.1.0-pre.1");
+    repo
+}
+
+fn tag_with_message(repo: &TestRepo, name: &str, message: &str) {
+    repo.execute(
+        &["tag", "-a", name, "-m", message],
+        &format!("create tag {name}"),
+    );
+}
+
+#[rstest]
+fn test_that_channel_is_read_from_annotated_tag_message(repo: TestRepo) {
+    tag_with_message(&repo, "v1.0.0", "Release candidate\n\nchannel: rc");
+
+    repo.assert().full_sem_ver("1.0.0-rc").channel("rc");
+    repo.commit_and_assert("1.1.0-pre.1").channel("rc");
+}
+
+#[rstest]
+fn test_that_channel_of_tag_message_is_ignored_as_release(mut repo: TestRepo) {
+    tag_with_message(&repo, "v1.0.0", "Release candidate\n\nchannel: rc");
+    repo.config.as_release = true;
+
+    repo.assert().full_sem_ver("1.0.0").channel("rc");
+}
+
+#[rstest]
+fn test_that_channel_of_tag_message_is_ignored_when_disabled(mut repo: TestRepo) {
+    tag_with_message(&repo, "v1.0.0", "Release candidate\n\nchannel: rc");
+    repo.config.tag_message_channel = false;
+
+    repo.assert().full_sem_ver("1.0.0").channel("");
+}
+
+#[rstest]
+fn test_that_tag_message_without_channel_keeps_release(repo: TestRepo) {
+    tag_with_message(&repo, "v1.0.0", "Release 1.0.0");
+
+    repo.assert().full_sem_ver("1.0.0").channel("");
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo() -> TestRepo {
+    let repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.commit("0.1.0-pre.1");
+    repo.tag("v1.0.0");
+    repo
+}
+
+#[rstest]
+fn test_tag_name_of_release_is_prefixed_major_minor_patch(mut repo: TestRepo) {
+    repo.config.build_metadata_mode = "commits".to_string();
+
+    repo.assert().full_sem_ver("1.0.0+0").tag_name("v1.0.0");
+}
+
+#[rstest]
+fn test_tag_name_of_pre_release_is_prefixed_full_sem_ver(repo: TestRepo) {
+    repo.commit_and_assert("1.1.0-pre.1")
+        .tag_name("v1.1.0-pre.1");
+}
+
+#[rstest]
+fn test_tag_name_of_pre_release_omits_build_metadata(mut repo: TestRepo) {
+    repo.config.build_metadata_mode = "commits".to_string();
+
+    repo.commit_and_assert("1.1.0-pre.1+1")
+        .tag_name("v1.1.0-pre.1");
+}
+
+#[rstest]
+fn test_tag_name_uses_preferred_tag_prefix_if_tag_prefix_is_a_pattern(mut repo: TestRepo) {
+    repo.config.tag_prefix = "(release-|[vV])?".to_string();
+    repo.config.preferred_tag_prefix = "release-".to_string();
+
+    repo.assert().tag_name("release-1.0.0");
+    repo.commit_and_assert("1.1.0-pre.1")
+        .tag_name("release-1.1.0-pre.1");
+}
+
+#[rstest]
+fn test_tag_name_ignores_sem_ver_format(mut repo: TestRepo) {
+    repo.config.sem_ver_format = "v{Major}.{Minor}.{Patch}".to_string();
+
+    repo.commit_and_assert("v1.1.0").tag_name("v1.1.0-pre.1");
+}
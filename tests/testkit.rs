@@ -0,0 +1,78 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use git_versioner::GitVersion;
+use git_versioner::testkit::TestRepo as KitRepo;
+use rstest::rstest;
+
+enum Step {
+    Commit(&'static str),
+    Branch(&'static str),
+    Checkout(&'static str),
+    Tag(&'static str),
+    Merge(&'static str),
+}
+
+use Step::*;
+
+// The variables independent of commit hashes and dates, which differ between both repositories
+fn comparable(version: &GitVersion) -> Vec<String> {
+    vec![
+        version.full_sem_ver.clone(),
+        version.branch_name.clone(),
+        version.pre_release_label.clone(),
+        version.pre_release_number.to_string(),
+        version.commits_since_version_source.to_string(),
+        version.version_source_kind.clone(),
+    ]
+}
+
+#[rstest]
+#[case::trunk(&[Tag("v1.0.0"), Commit("feat: new feature")])]
+#[case::feature_branch(&[Tag("v1.0.0"), Branch("feature/login"), Commit("feature commit")])]
+#[case::release_branch(&[Branch("release/1.0.0"), Commit("release commit")])]
+#[case::merged_release_branch(&[
+    Branch("release/1.0.0"),
+    Commit("release commit"),
+    Tag("v1.0.0"),
+    Checkout(MAIN_BRANCH),
+    Commit("trunk commit"),
+    Merge("release/1.0.0"),
+])]
+fn test_testkit_repository_yields_same_version_as_git_executable(#[case] steps: &[Step]) {
+    let repo = TestRepo::initialize(MAIN_BRANCH);
+    let kit_repo = KitRepo::initialize(MAIN_BRANCH);
+    repo.commit("initial commit");
+    kit_repo.commit("initial commit");
+    for step in steps {
+        match step {
+            Commit(message) => {
+                repo.commit(message);
+                kit_repo.commit(message);
+            }
+            Branch(name) => {
+                repo.branch(name);
+                kit_repo.branch(name);
+            }
+            Checkout(name) => {
+                repo.checkout(name);
+                kit_repo.checkout(name);
+            }
+            Tag(name) => {
+                repo.tag(name);
+                kit_repo.tag(name);
+            }
+            Merge(name) => {
+                repo.merge(name);
+                kit_repo.merge(name);
+            }
+        }
+    }
+
+    assert_eq!(
+        comparable(&kit_repo.assert()),
+        comparable(&repo.assert().result),
+        "{}",
+        repo.graph()
+    );
+}
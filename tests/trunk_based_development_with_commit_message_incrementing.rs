@@ -12,7 +12,7 @@ fn repo(#[default(MAIN_BRANCH)] main_branch: &str) -> TestRepo {
 
 #[rstest]
 #[should_panic(
-    expected = r#"Invalid value "foo" for CommitMessageIncrementing. Should be "Enabled" or "Disabled"."#
+    expected = r#"Invalid value "foo" for CommitMessageIncrementing. Should be "Enabled", "Disabled" or "MergeMessageOnly"."#
 )]
 fn test_providing_non_disabled_or_enabled_string_to_commit_message_incrementing_panics(
     mut repo: TestRepo,
@@ -101,3 +101,81 @@ fn test_on_main_branch_with_major_version_greater_than_zero_when_encountering_co
     repo.commit("fix: foo\n\nBody\n\nBREAKING CHANGE: bar");
     repo.commit_and_assert("2.0.0-pre.2");
 }
+
+#[rstest]
+#[should_panic(
+    expected = r#"Invalid value "Mainline" for BumpScan. Should be "All" or "FirstParent"."#
+)]
+fn test_providing_unknown_bump_scan_panics(mut repo: TestRepo) {
+    repo.config.bump_scan = "Mainline".to_string();
+    repo.assert(); // panics
+}
+
+#[rstest]
+#[case::all("All", "2.0.0-pre.2")]
+#[case::first_parent("FirstParent", "1.0.1-pre.2")]
+fn test_bump_scan_determines_whether_breaking_change_on_merged_branch_bumps_major_version(
+    mut repo: TestRepo,
+    #[case] bump_scan: &str,
+    #[case] expected: &str,
+) {
+    repo.config.bump_scan = bump_scan.to_string();
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.tag_and_assert("v", "1.0.0");
+    repo.branch("feature/foo");
+    repo.commit("feat!: foo");
+    repo.checkout(MAIN_BRANCH);
+    repo.merge("feature/foo");
+
+    repo.assert().full_sem_ver(expected);
+}
+
+#[rstest]
+fn test_first_parent_bump_scan_considers_merge_messages(mut repo: TestRepo) {
+    repo.config.bump_scan = "FirstParent".to_string();
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.tag_and_assert("v", "1.0.0");
+    repo.branch("feature/foo");
+    repo.commit("fix: foo");
+    repo.checkout(MAIN_BRANCH);
+    repo.execute(
+        &["merge", "--no-ff", "feature/foo", "-m", "feat!: foo"],
+        "merge feature/foo",
+    );
+
+    repo.assert().full_sem_ver("2.0.0-pre.2");
+}
+
+#[rstest]
+fn test_merge_message_only_ignores_feature_commits_inside_merged_branch(mut repo: TestRepo) {
+    repo.config.commit_message_incrementing = "MergeMessageOnly".to_string();
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.tag_and_assert("v", "1.0.0");
+    repo.branch("feature/foo");
+    repo.commit("feat: foo");
+    repo.checkout(MAIN_BRANCH);
+    repo.merge("feature/foo");
+
+    repo.assert().full_sem_ver("1.0.1-pre.2");
+}
+
+#[rstest]
+fn test_merge_message_only_bumps_minor_version_on_feature_merge_message(
+    mut repo: TestRepo,
+    #[values(&["feat: foo"], &["Merge pull request #1 from user/feature/foo", "feat: foo"])]
+    messages: &[&str],
+) {
+    repo.config.commit_message_incrementing = "MergeMessageOnly".to_string();
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.tag_and_assert("v", "1.0.0");
+    repo.branch("feature/foo");
+    repo.commit("fix: foo");
+    repo.checkout(MAIN_BRANCH);
+    let mut command = vec!["merge", "--no-ff", "feature/foo"];
+    for message in messages {
+        command.extend(["-m", *message]);
+    }
+    repo.execute(&command, "merge feature/foo");
+
+    repo.assert().full_sem_ver("1.1.0-pre.2");
+}
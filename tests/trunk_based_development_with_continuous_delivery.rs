@@ -107,6 +107,17 @@ fn test_that_on_release_branch_when_no_tags_exist_produces_pre_release_tag_1(rep
         .version_source_sha("");
 }
 
+#[rstest]
+fn test_that_release_branch_pattern_matches_case_insensitively_when_enabled(mut repo: TestRepo) {
+    repo.config.case_insensitive_branches = true;
+    repo.branch("Release/1.0.0");
+    repo.commit("1.0.0+1");
+
+    repo.assert()
+        .full_sem_ver("1.0.0-pre.1")
+        .version_source_sha("");
+}
+
 #[rstest]
 fn test_that_on_release_branch_when_release_tags_exist_produces_pre_release_tag_1(repo: TestRepo) {
     repo.branch("release/1.0.0");
@@ -182,3 +193,119 @@ fn test_that_on_release_branch_with_unique_version_when_pre_release_tag_exist_pr
         .full_sem_ver("1.0.0-pre.2")
         .version_source_sha(&sha);
 }
+
+#[rstest]
+fn test_that_pre_release_tags_on_unrelated_branches_are_ignored(repo: TestRepo) {
+    repo.branch("abandoned");
+    repo.commit("abandoned");
+    repo.tag("v0.1.0-pre.7");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit("0.1.0+2");
+
+    repo.assert()
+        .full_sem_ver("0.1.0-pre.1")
+        .version_source_sha("");
+}
+
+#[rstest]
+fn test_that_release_branches_with_same_version_do_not_share_pre_release_numbers(repo: TestRepo) {
+    repo.branch("release/1.0.0");
+    repo.commit("release/1.0.0");
+    repo.tag("v1.0.0-pre.1");
+    repo.commit("release/1.0.0");
+    repo.tag("v1.0.0-pre.2");
+    repo.checkout(MAIN_BRANCH);
+    repo.branch("releases/1.0.0");
+    // Distinct message, an identical commit would share the tags of release/1.0.0
+    repo.commit("releases/1.0.0");
+
+    repo.assert()
+        .full_sem_ver("1.0.0-pre.1")
+        .version_source_sha("");
+}
+
+#[rstest]
+fn test_that_release_tag_on_head_wins_over_pre_release_tag_on_head(mut repo: TestRepo) {
+    repo.config.pre_release_tag = "rc".to_string();
+    repo.commit("1.2.0+1");
+    let (sha, _) = repo.tag("v1.3.0-rc.1");
+    repo.tag("v1.2.0");
+
+    repo.assert()
+        .full_sem_ver("1.2.0")
+        .weighted_pre_release_number(60000)
+        .version_source_sha(&sha);
+}
+
+#[rstest]
+fn test_that_feature_branch_named_like_pre_release_tag_does_not_affect_pre_release_numbers(
+    repo: TestRepo,
+) {
+    repo.tag("v0.1.0-pre.5");
+    repo.branch("feature/pre");
+    repo.commit("0.1.0-pre-feature.1");
+
+    repo.assert().full_sem_ver("0.1.0-pre-feature.1");
+
+    repo.tag("v0.1.0-pre-feature.1");
+    repo.checkout(MAIN_BRANCH);
+    repo.merge("feature/pre");
+
+    repo.assert().full_sem_ver("0.1.0-pre.6");
+}
+
+#[rstest]
+fn test_that_feature_label_prefix_disambiguates_feature_branch_named_like_pre_release_tag(
+    mut repo: TestRepo,
+) {
+    repo.config.feature_label_prefix = "f-".to_string();
+    repo.tag("v0.1.0-pre.5");
+    repo.branch("feature/pre");
+    repo.commit("0.1.0-f-pre.1");
+
+    repo.assert().full_sem_ver("0.1.0-f-pre.1");
+
+    repo.tag("v0.1.0-f-pre.1");
+    repo.checkout(MAIN_BRANCH);
+    repo.merge("feature/pre");
+
+    repo.assert().full_sem_ver("0.1.0-pre.6");
+}
+
+#[rstest]
+fn test_that_feature_label_prefix_is_escaped_and_prepended_to_feature_labels(mut repo: TestRepo) {
+    repo.config.feature_label_prefix = "f/".to_string();
+    repo.tag("v0.1.0-pre.1");
+    repo.branch("feature/my-feature");
+    repo.commit("0.1.0-f-my-feature.1");
+
+    repo.assert().full_sem_ver("0.1.0-f-my-feature.1");
+}
+
+#[rstest]
+fn test_that_annotated_pre_release_tag_is_continued_like_lightweight_tag(repo: TestRepo) {
+    repo.tag("v1.0.0");
+    let (sha, _) = repo.commit("1.1.0+1");
+    repo.tag_annotated("v1.1.0-pre.1");
+    repo.commit("1.1.0+2");
+
+    repo.assert()
+        .full_sem_ver("1.1.0-pre.2")
+        .version_source_sha(&sha);
+}
+
+#[rstest]
+fn test_that_nested_annotated_pre_release_tag_resolves_to_tagged_commit(repo: TestRepo) {
+    repo.tag("v1.0.0");
+    let (sha, _) = repo.commit("1.1.0+1");
+    repo.tag_annotated("nightly");
+    repo.execute(
+        &["tag", "-a", "v1.1.0-pre.1", "-m", "v1.1.0-pre.1", "nightly"],
+        "create nested tag v1.1.0-pre.1",
+    );
+    repo.commit("1.1.0+2");
+
+    repo.assert()
+        .full_sem_ver("1.1.0-pre.2")
+        .version_source_sha(&sha);
+}
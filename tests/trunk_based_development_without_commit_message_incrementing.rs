@@ -1,6 +1,7 @@
 mod common;
 
 use crate::common::{MAIN_BRANCH, TestRepo, VisualizableRepo};
+use git_versioner::GitVersioner;
 use rstest::{fixture, rstest};
 
 struct ScopedEnvVar {
@@ -173,6 +174,29 @@ fn test_result_on_detached_head_is_no_branch(repo: TestRepo) {
         .escaped_branch_name("-no-branch-");
 }
 
+#[rstest]
+fn test_result_on_symbolic_reference_chain_is_the_final_branch(repo: TestRepo) {
+    repo.commit("0.1.0-pre.1");
+    repo.branch("release/1.0.0");
+    repo.execute(
+        &[
+            "symbolic-ref",
+            "refs/heads/ci-head",
+            "refs/heads/release/1.0.0",
+        ],
+        "create symbolic reference ci-head",
+    );
+    repo.execute(
+        &["symbolic-ref", "HEAD", "refs/heads/ci-head"],
+        "point HEAD at ci-head",
+    );
+    repo.commit("1.0.0-pre.1");
+
+    repo.assert()
+        .full_sem_ver("1.0.0-pre.1")
+        .branch_name("release/1.0.0");
+}
+
 #[rstest]
 fn test_result_on_checked_out_version_tag_is_the_tag_version(repo: TestRepo) {
     let (sha, _) = repo.commit("commit");
@@ -222,6 +246,193 @@ fn test_release_branches_may_only_define_partial_semantic_version(repo: TestRepo
     repo.commit_and_assert("1.1.0-pre.1");
 }
 
+#[rstest]
+fn test_release_branches_may_only_define_major_version(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.branch("release/2");
+    repo.commit_and_assert("2.0.0-pre.1");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit_and_assert("2.1.0-pre.1");
+}
+
+#[rstest]
+fn test_release_branches_with_trailing_text_use_leading_version(
+    repo: TestRepo,
+    #[values("release/1.0.0/something", "release/1.0.0rc", "release/1.0_hotfix")] name: &str,
+) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.branch(name);
+    repo.commit_and_assert("1.0.0-pre.1");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit_and_assert("1.1.0-pre.1");
+}
+
+#[rstest]
+fn test_release_branches_with_pre_release_version_are_no_release_branches(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.branch("release/1.0.0-rc");
+    repo.commit_and_assert("0.1.0-release-1-0-0-rc.1");
+}
+
+#[rstest]
+fn test_release_branches_with_four_component_version_ignore_fourth_component(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.branch("release/1.2.0.7");
+    repo.commit_and_assert("1.2.0-pre.1");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit_and_assert("1.3.0-pre.1");
+}
+
+#[rstest]
+fn test_release_tags_with_four_component_version_are_ignored_by_default(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.tag("v1.2.3.4");
+    repo.commit_and_assert("0.1.0-pre.2");
+}
+
+#[rstest]
+fn test_release_tags_with_loose_version_are_considered_when_enabled(
+    mut repo: TestRepo,
+    #[values(("v2", "2.0.0"), ("v2.1", "2.1.0"), ("v2.1.0.4", "2.1.0"))] tag: (&str, &str),
+) {
+    let (tag, expected) = tag;
+    repo.config.loose_tag_versions = true;
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.tag(tag);
+    repo.assert().full_sem_ver(expected);
+}
+
+#[rstest]
+fn test_version_source_kind_without_source_is_none(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1")
+        .version_source_sha("")
+        .version_source_kind("None");
+}
+
+#[rstest]
+fn test_version_source_kind_from_release_tag_is_tag(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.tag("v1.0.0");
+    repo.commit_and_assert("1.1.0-pre.1")
+        .version_source_kind("Tag");
+}
+
+#[rstest]
+fn test_version_source_kind_from_release_branch_is_branch(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.branch("release/1.0.0");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit_and_assert("1.1.0-pre.1")
+        .version_source_kind("Branch");
+}
+
+#[rstest]
+fn test_commits_since_release_branch_without_release_branch_is_negative(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1")
+        .commits_since_release_branch(-1);
+}
+
+#[rstest]
+fn test_commits_since_release_branch_on_trunk_counts_commits_after_branching(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.branch("release/1.0.0");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit_and_assert("1.1.0-pre.1")
+        .commits_since_release_branch(1);
+    repo.commit_and_assert("1.1.0-pre.2")
+        .commits_since_release_branch(2);
+}
+
+#[rstest]
+fn test_feature_branched_from_release_branch_is_based_on_release_branch(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.tag("v1.0.0");
+    repo.branch("release/1.0.0");
+    repo.commit_and_assert("1.0.1-pre.1");
+    repo.branch("feature/fix");
+    repo.commit_and_assert("1.0.1-fix.1")
+        .commits_since_release_branch(1);
+    repo.commit_and_assert("1.0.1-fix.2")
+        .commits_since_release_branch(2);
+}
+
+#[rstest]
+fn test_version_source_distance_on_release_branch_past_tag(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    let (sha, _) = repo.tag("v1.0.0");
+    repo.branch("release/1.0.0");
+    repo.commit("1.0.1-pre.1");
+    repo.commit("1.0.1-pre.2");
+    repo.commit_and_assert("1.0.1-pre.3")
+        .version_source_sha(&sha)
+        .version_source_distance(3)
+        .commits_since_version_source(3);
+}
+
+#[rstest]
+fn test_version_source_distance_counts_commits_to_merge_base_with_version_source(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.branch("release/1.0.0");
+    repo.commit("1.0.0-pre.2");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit("1.1.0-pre.1");
+    repo.commit_and_assert("1.1.0-pre.2")
+        .version_source_distance(2);
+}
+
+#[rstest]
+fn test_commits_since_release_branch_uses_nearest_release_branch(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.branch("release/1.0.0");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit_and_assert("1.1.0-pre.1");
+    repo.branch("release/1.1.0");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit_and_assert("1.2.0-pre.1");
+    repo.branch("feature/my-feature");
+    repo.commit("1.2.0-my-feature.1");
+    repo.assert().commits_since_release_branch(2);
+}
+
+#[rstest]
+fn test_release_tag_takes_precedence_over_release_branch_with_same_version(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    let (sha, _) = repo.tag("v1.1.0");
+    repo.branch("release/1.1.0");
+    repo.commit("1.1.1-pre.1");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit("1.2.0-pre.1");
+
+    let expected = repo
+        .assert()
+        .full_sem_ver("1.2.0-pre.1")
+        .version_source_sha(&sha)
+        .version_source_kind("Tag")
+        .result;
+    for _ in 0..20 {
+        assert_eq!(
+            GitVersioner::calculate_version(&repo.config).unwrap(),
+            expected
+        );
+    }
+}
+
+#[rstest]
+fn test_tag_selection_prefers_most_recent_commit_among_equal_versions_when_configured(
+    mut repo: TestRepo,
+) {
+    repo.config.tag_selection = "most-recent-commit".to_string();
+    repo.commit_at("0.1.0-pre.1", "2024-05-01T12:00:00Z");
+    repo.tag("v1.0.0");
+    let (sha, _) = repo.commit_at("1.1.0-pre.1", "2024-05-02T12:00:00Z");
+    repo.tag("1.0.0");
+    repo.commit_at("1.1.0-pre.1", "2024-05-03T12:00:00Z");
+
+    repo.assert()
+        .full_sem_ver("1.1.0-pre.1")
+        .version_source_sha(&sha);
+}
+
 #[rstest]
 fn test_release_branches_matching_initial_trunk_version_continue_release_at_version_root(
     repo: TestRepo,
@@ -260,7 +471,7 @@ fn test_release_branches_matching_release_branch_incremented_trunk_version_conti
 
 #[rstest]
 fn test_release_branches_matching_custom_pattern_affect_main_branch(mut repo: TestRepo) {
-    repo.config.release_branch = r"^stabilize/my/(?<BranchName>.+)$".to_string();
+    repo.config.release_branch = r"^stabilize/my/(?<BranchName>.+)$".into();
 
     let (source, _) = repo.commit("0.1.0-pre.1");
     repo.branch("stabilize/my/1.0.0");
@@ -270,9 +481,38 @@ fn test_release_branches_matching_custom_pattern_affect_main_branch(mut repo: Te
         .version_source_sha(&source);
 }
 
+#[rstest]
+fn test_release_branches_matching_any_of_multiple_patterns_affect_main_branch(
+    mut repo: TestRepo,
+    #[values("rel/1.0.0", "release/1.0.0")] release_branch: &str,
+) {
+    repo.config.release_branch =
+        [r"^releases?/(?<BranchName>.+)$", r"^rel/(?<BranchName>.+)$"].into();
+
+    let (source, _) = repo.commit("0.1.0-pre.1");
+    repo.branch(release_branch);
+    repo.checkout(MAIN_BRANCH);
+    repo.commit_and_assert("1.1.0-pre.1")
+        .version_source_sha(&source);
+}
+
+#[rstest]
+fn test_release_branch_matching_second_of_multiple_patterns_creates_expected_prerelease(
+    mut repo: TestRepo,
+) {
+    repo.config.release_branch =
+        [r"^releases?/(?<BranchName>.+)$", r"^rel/(?<BranchName>.+)$"].into();
+
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.tag("v1.0.0");
+    repo.branch("rel/1.0.0");
+    repo.commit_and_assert("1.0.1-pre.1")
+        .branch_name("rel/1.0.0");
+}
+
 #[rstest]
 fn test_release_branches_matching_custom_pattern_create_expected_prerelease(mut repo: TestRepo) {
-    repo.config.release_branch = r"^stabilize/my/(?<BranchName>.+)$".to_string();
+    repo.config.release_branch = r"^stabilize/my/(?<BranchName>.+)$".into();
 
     repo.commit_and_assert("0.1.0-pre.1");
     let (source, _) = repo.tag("v1.0.0");
@@ -304,6 +544,32 @@ fn test_release_branches_not_matching_current_trunk_version_increment_start_new_
     repo.commit_and_assert("1.2.0-pre.1");
 }
 
+#[rstest]
+fn test_release_branches_without_common_history_start_new_release_at_their_root(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.execute(
+        &["checkout", "--orphan", "release/2.0.0"],
+        "create orphan branch release/2.0.0",
+    );
+
+    repo.commit_and_assert("2.0.0-pre.1").version_source_sha("");
+    repo.commit_and_assert("2.0.0-pre.2");
+}
+
+#[rstest]
+fn test_release_branches_without_common_history_count_all_commits_on_main_branch(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.execute(
+        &["checkout", "--orphan", "release/1.0.0"],
+        "create orphan branch release/1.0.0",
+    );
+    let (sha, _) = repo.commit("1.0.0-pre.1");
+    repo.checkout(MAIN_BRANCH);
+
+    repo.commit_and_assert("1.1.0-pre.2")
+        .version_source_sha(&sha);
+}
+
 #[rstest]
 fn test_release_tags_with_matching_version_tag_prefix_are_considered(
     repo: TestRepo,
@@ -314,6 +580,28 @@ fn test_release_tags_with_matching_version_tag_prefix_are_considered(
         .version_source_sha(&source_sha);
 }
 
+#[rstest]
+fn test_next_version_tag_uses_preferred_tag_prefix(
+    mut repo: TestRepo,
+    #[values(
+        ("[vV]?", "v"),
+        ("my/v", "my/v"),
+        ("(release|rel)-", "release-"),
+        ("(release-)?v", "v"),
+        (r"v\d{0}", "v"),
+        ("", "")
+    )]
+    prefix: (&str, &str),
+) {
+    let (pattern, preferred) = prefix;
+    repo.config.tag_prefix = pattern.to_string();
+    repo.config.preferred_tag_prefix = preferred.to_string();
+    repo.commit("0.1.0-pre.1");
+    repo.tag(&format!("{preferred}1.2.0"));
+    repo.commit_and_assert("1.3.0-pre.1")
+        .next_version_tag(&format!("{preferred}1.3.0"));
+}
+
 #[rstest]
 fn test_prerelease_tags_with_matching_version_tag_prefix_are_ignored(repo: TestRepo) {
     repo.commit_and_assert("0.1.0-pre.1");
@@ -378,7 +666,7 @@ fn test_feature_branches_from_feature_branches_extend_source_feature_branch(repo
 fn test_feature_branches_matching_custom_pattern_inherit_source_branch_base_version(
     mut repo: TestRepo,
 ) {
-    repo.config.feature_branch = r"^feat(ure)?/(?<BranchName>.+)$".to_string();
+    repo.config.feature_branch = r"^feat(ure)?/(?<BranchName>.+)$".into();
 
     repo.commit_and_assert("0.1.0-pre.1");
     let (source, _) = repo.tag("v1.0.0");
@@ -405,6 +693,24 @@ fn test_valid_feature_branch_symbols_incompatible_with_semantic_versions_are_rep
         .branch_name(branch_name);
 }
 
+#[rstest]
+#[case("🎉🎉", "a801aca")]
+#[case("___", "da706a0")]
+#[case("#+#", "4cb5f76")]
+fn test_feature_branch_names_without_compatible_symbols_use_hashed_fallback_label(
+    repo: TestRepo,
+    #[case] name: &str,
+    #[case] hash: &str,
+) {
+    let branch_name = &format!("feature/{name}");
+
+    repo.commit("irrelevant");
+    repo.branch(branch_name);
+
+    repo.commit_and_assert(&format!("0.1.0-branch-{hash}.1"))
+        .branch_name(branch_name);
+}
+
 #[rstest]
 fn test_non_matching_branches_are_treated_as_feature_branches(repo: TestRepo) {
     repo.commit_and_assert("0.1.0-pre.1");
@@ -416,13 +722,33 @@ fn test_non_matching_branches_are_treated_as_feature_branches(repo: TestRepo) {
 #[rstest]
 fn test_weighted_prerelease_number_for_main_branch_adds_55000(repo: TestRepo) {
     repo.commit_and_assert("0.1.0-pre.1")
-        .weighted_pre_release_number(55001);
+        .weighted_pre_release_number(55001)
+        .weight_source("Main");
 }
 
 #[rstest]
 fn test_weighted_prerelease_number_for_main_branch_release_tag_adds_60000(repo: TestRepo) {
     repo.commit_and_assert("0.1.0-pre.1");
     repo.tag_and_assert("v", "1.0.0")
+        .weighted_pre_release_number(60000)
+        .weight_source("Tag");
+}
+
+#[rstest]
+fn test_release_tag_on_head_wins_over_pre_release_tag_on_head(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.tag("v1.3.0-rc.1");
+    repo.tag_and_assert("v", "1.2.0")
+        .weighted_pre_release_number(60000)
+        .weight_source("Tag");
+}
+
+#[rstest]
+fn test_release_tag_on_feature_branch_head_yields_release_version(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.branch("feature/hotfix");
+    repo.commit_and_assert("0.1.0-hotfix.1");
+    repo.tag_and_assert("v", "0.1.0")
         .weighted_pre_release_number(60000);
 }
 
@@ -465,7 +791,8 @@ fn test_weighted_prerelease_number_for_release_branch_adds_55000(repo: TestRepo)
     repo.branch("release/0.1.0");
 
     repo.commit_and_assert("0.1.0-pre.2")
-        .weighted_pre_release_number(55002);
+        .weighted_pre_release_number(55002)
+        .weight_source("Release");
 }
 
 #[rstest]
@@ -535,7 +862,8 @@ fn test_weighted_prerelease_number_for_feature_branch_adds_30000(repo: TestRepo)
     repo.branch("feature/feature-A");
 
     repo.commit_and_assert("0.1.0-feature-A.1")
-        .weighted_pre_release_number(30001);
+        .weighted_pre_release_number(30001)
+        .weight_source("Feature");
 }
 
 #[rstest]
@@ -610,3 +938,104 @@ fn test_custom_prerelease_tag(mut repo: TestRepo) {
         .branch_name(MAIN_BRANCH)
         .version_source_sha("");
 }
+
+#[rstest]
+#[case::plain("release-notes")]
+#[case::with_version_suffix("release-notes-v1")]
+#[case::with_version_like_suffix("notes-1.0.0")]
+fn test_tags_not_starting_with_a_version_are_ignored(mut repo: TestRepo, #[case] tag: &str) {
+    repo.config.loose_tag_versions = true;
+    repo.commit("0.1.0-pre.1");
+    repo.tag(tag);
+
+    repo.commit_and_assert("0.1.0-pre.2").version_source_sha("");
+}
+
+#[rstest]
+fn test_tag_prefix_with_alternatives_detects_version_after_any_of_them(mut repo: TestRepo) {
+    repo.config.tag_prefix = "v|version-".to_string();
+    repo.commit("0.1.0-pre.1");
+    repo.tag("version-1.0.0");
+
+    repo.commit_and_assert("1.1.0-pre.1");
+}
+
+#[rstest]
+fn test_describe_is_tag_name_on_tagged_commit(repo: TestRepo) {
+    repo.commit("0.1.0-pre.1");
+
+    repo.tag_and_assert("v", "1.0.0").describe("v1.0.0");
+}
+
+#[rstest]
+fn test_describe_counts_commits_since_tag(repo: TestRepo) {
+    repo.commit("0.1.0-pre.1");
+    repo.tag("v1.0.0");
+    let (sha, _) = repo.commit("1.1.0-pre.1");
+
+    repo.assert()
+        .full_sem_ver("1.1.0-pre.1")
+        .describe(&format!("v1.0.0-1-g{}", &sha[..7]));
+}
+
+#[rstest]
+fn test_describe_is_empty_without_tag(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1").describe("");
+}
+
+#[rstest]
+fn test_release_branch_versions_compare_numerically(repo: TestRepo) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.branch("release/1.9.0");
+    repo.checkout(MAIN_BRANCH);
+    repo.commit("1.10.0-pre.1");
+    repo.branch("release/1.10.0");
+    repo.checkout(MAIN_BRANCH);
+
+    repo.commit_and_assert("1.11.0-pre.1");
+}
+
+#[rstest]
+fn test_channel_is_captured_from_tag_prefix_of_version_source(mut repo: TestRepo) {
+    repo.config.tag_prefix = "(?<Channel>alpha|beta)-v".to_string();
+    repo.commit("0.1.0-pre.1");
+
+    repo.tag_and_assert("beta-v", "1.0.0").channel("beta");
+    repo.commit_and_assert("1.1.0-pre.1").channel("beta");
+}
+
+#[rstest]
+fn test_channel_is_empty_without_channel_group(repo: TestRepo) {
+    repo.commit("0.1.0-pre.1");
+
+    repo.tag_and_assert("v", "1.0.0").channel("");
+}
+
+#[rstest]
+#[case::branched_from_trunk(MAIN_BRANCH)]
+#[case::branched_from_release_branch("release/1.1.0")]
+fn test_feature_branch_from_tip_shared_by_trunk_and_release_branch_is_based_on_trunk(
+    repo: TestRepo,
+    #[case] start: &str,
+) {
+    repo.commit_and_assert("0.1.0-pre.1");
+    repo.tag_and_assert("v", "1.0.0");
+    repo.commit_and_assert("1.1.0-pre.1");
+    repo.branch("release/1.1.0");
+
+    for branch in [MAIN_BRANCH, "release/1.1.0"] {
+        repo.checkout(branch);
+        let expected = GitVersioner::calculate_version(&repo.config).unwrap();
+        for _ in 0..20 {
+            assert_eq!(
+                GitVersioner::calculate_version(&repo.config).unwrap(),
+                expected
+            );
+        }
+    }
+
+    repo.checkout(start);
+    repo.branch("feature/my-feature");
+    repo.commit_and_assert("1.2.0-my-feature.1");
+    repo.commit_and_assert("1.2.0-my-feature.2");
+}
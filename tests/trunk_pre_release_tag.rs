@@ -0,0 +1,48 @@
+mod common;
+
+use crate::common::{MAIN_BRANCH, TestRepo};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn repo() -> TestRepo {
+    let mut repo = TestRepo::initialize(MAIN_BRANCH);
+    repo.config.pre_release_tag = "rc".to_string();
+    repo.config.trunk_pre_release_tag = "ci".to_string();
+    repo.commit("0.1.0-ci.1");
+    repo.tag("v1.0.0");
+    repo
+}
+
+#[rstest]
+fn test_trunk_uses_pre_release_tag_if_trunk_pre_release_tag_is_unspecified(mut repo: TestRepo) {
+    repo.config.trunk_pre_release_tag = String::new();
+
+    repo.commit_and_assert("1.1.0-rc.1");
+}
+
+#[rstest]
+fn test_trunk_and_release_branches_use_separate_pre_release_tags(repo: TestRepo) {
+    repo.branch("release/1.0.0");
+
+    repo.checkout(MAIN_BRANCH);
+    repo.commit_and_assert("1.1.0-ci.1")
+        .pre_release_label_with_dash("-ci");
+
+    repo.checkout("release/1.0.0");
+    repo.commit_and_assert("1.0.1-rc.1")
+        .pre_release_label_with_dash("-rc");
+}
+
+#[rstest]
+fn test_continuous_delivery_continues_trunk_pre_release_tags(mut repo: TestRepo) {
+    repo.config.continuous_delivery = true;
+    repo.commit_and_assert("1.1.0-ci.1");
+    repo.tag("v1.1.0-ci.1");
+    repo.commit_and_assert("1.1.0-ci.2");
+}
+
+#[rstest]
+fn test_feature_branch_named_like_trunk_pre_release_tag_gets_suffix(repo: TestRepo) {
+    repo.branch("feature/ci");
+    repo.commit_and_assert("1.1.0-ci-feature.1");
+}